@@ -0,0 +1,95 @@
+use std::path::PathBuf;
+
+use colored::*;
+
+/// Which Homebrew installation a project targets.
+///
+/// On machines with both Intel Homebrew (`/usr/local`, possibly running
+/// under Rosetta) and Apple Silicon Homebrew (`/opt/homebrew`), a bare
+/// `brew` on `PATH` can resolve to either one. The selected `BrewVariant`
+/// is used to warn when a package prefix resolved through the `brew` on
+/// `PATH` lies outside it; it does not change which `brew` is run or where
+/// symlinks point.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BrewVariant {
+    /// Apple Silicon Homebrew at `/opt/homebrew`
+    MacArm,
+    /// Intel (or Rosetta) Homebrew at `/usr/local`
+    MacIntel,
+    /// An explicit, user-supplied brew prefix
+    Path(PathBuf),
+}
+
+impl BrewVariant {
+    const ARM_PREFIX: &'static str = "/opt/homebrew";
+    const INTEL_PREFIX: &'static str = "/usr/local";
+
+    /// The Homebrew prefix this variant resolves to
+    pub fn prefix(&self) -> PathBuf {
+        match self {
+            BrewVariant::MacArm => PathBuf::from(Self::ARM_PREFIX),
+            BrewVariant::MacIntel => PathBuf::from(Self::INTEL_PREFIX),
+            BrewVariant::Path(p) => p.clone(),
+        }
+    }
+
+    fn prefix_has_brew(prefix: &str) -> bool {
+        PathBuf::from(prefix).join("bin/brew").exists()
+    }
+
+    /// Probe the machine for every Homebrew installation actually present
+    pub fn detect_all() -> Vec<BrewVariant> {
+        let mut found = Vec::new();
+        if Self::prefix_has_brew(Self::ARM_PREFIX) {
+            found.push(BrewVariant::MacArm);
+        }
+        if Self::prefix_has_brew(Self::INTEL_PREFIX) {
+            found.push(BrewVariant::MacIntel);
+        }
+        found
+    }
+
+    /// Parse a manifest/CLI `arch` value (e.g. `"arm"`, `"intel"`, or an
+    /// explicit path) into a variant
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "arm" | "macarm" | "apple-silicon" => BrewVariant::MacArm,
+            "intel" | "macintel" => BrewVariant::MacIntel,
+            other => BrewVariant::Path(PathBuf::from(other)),
+        }
+    }
+
+    /// Select the variant a project should use: the one pinned via `pinned`
+    /// if given, otherwise the only one present. If both are present, warn
+    /// about the ambiguity and fall back to the native Apple Silicon prefix;
+    /// if neither is found, fall back to it without a warning.
+    pub fn select(pinned: Option<&str>) -> Self {
+        if let Some(pinned) = pinned {
+            return Self::parse(pinned);
+        }
+
+        match Self::detect_all().as_slice() {
+            [single] => single.clone(),
+            [] => BrewVariant::MacArm,
+            _ => {
+                println!(
+                    "  {} Both Intel and Apple Silicon Homebrew installs were found; \
+                     defaulting to Apple Silicon ({}). Pin `arch` in the manifest to silence this.",
+                    "⚠".yellow(),
+                    Self::ARM_PREFIX
+                );
+                BrewVariant::MacArm
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for BrewVariant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BrewVariant::MacArm => write!(f, "arm"),
+            BrewVariant::MacIntel => write!(f, "intel"),
+            BrewVariant::Path(p) => write!(f, "{}", p.display()),
+        }
+    }
+}
@@ -13,7 +13,11 @@ pub struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// Initialize a new macdev environment
-    Init,
+    Init {
+        /// Pin the Homebrew variant this project targets ("arm", "intel", or an explicit prefix path)
+        #[arg(long)]
+        arch: Option<String>,
+    },
     
     /// Add packages to the environment
     Add {
@@ -23,17 +27,56 @@ enum Commands {
         /// Make packages available system-wide (impure)
         #[arg(long)]
         impure: bool,
+
+        /// Install as a Homebrew Cask (GUI application) instead of a formula
+        #[arg(long)]
+        cask: bool,
+
+        /// Only report what would be installed, without changing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Isolate into the project even if a system-wide (impure) install already satisfies it
+        #[arg(long)]
+        force_isolate: bool,
     },
 
     /// Remove packages from the environment
     Remove {
         /// Package names
         packages: Vec<String>,
+
+        /// Remove a Homebrew Cask instead of a formula
+        #[arg(long)]
+        cask: bool,
+
+        /// Only report what would be removed, without changing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Continue past individual failures instead of aborting, and report a summary at the end
+        #[arg(long)]
+        force: bool,
     },
-    
+
     /// Install all packages from manifest
-    Install,
-    
+    Install {
+        /// Only report what would be installed, without changing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Require the lock file to be up to date with the manifest, and
+        /// install the exact versions/formulas it records instead of
+        /// re-resolving them
+        #[arg(long)]
+        locked: bool,
+
+        /// Like --locked, but also refuse to fetch anything not already
+        /// available locally, so the install runs fully offline
+        #[arg(long)]
+        frozen: bool,
+    },
+
     /// Enter the isolated shell environment
     Shell,
 
@@ -41,10 +84,34 @@ enum Commands {
     List,
 
     /// Sync packages from manifest(s)
-    Sync,
+    Sync {
+        /// Only report what would be synced, without changing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Require the lock file to exist and be up to date with the
+        /// manifest before syncing. Missing pure packages are still
+        /// installed from the manifest's specs, and adding them regenerates
+        /// the lock file
+        #[arg(long)]
+        locked: bool,
+
+        /// Like --locked, but also refuse to fetch anything not already
+        /// available locally, so the sync runs fully offline
+        #[arg(long)]
+        frozen: bool,
+    },
 
     /// Garbage collect unused packages
-    Gc,
+    Gc {
+        /// Only report what would be removed, without changing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Also remove dependency-only packages whose refcount has dropped to zero
+        #[arg(long)]
+        autoremove: bool,
+    },
 
     /// Check if environment needs setup (exits 1 if install needed)
     Check {
@@ -57,18 +124,56 @@ enum Commands {
     Upgrade {
         /// Package to upgrade (upgrades all if not specified)
         package: Option<String>,
+
+        /// Ignore the lock file and upgrade to the newest version Homebrew offers
+        #[arg(long, conflicts_with = "to_lock")]
+        latest: bool,
+
+        /// Resync packages against the lock file instead of upgrading (the default when no package is named)
+        #[arg(long, conflicts_with = "latest")]
+        to_lock: bool,
     },
 
-    /// Add a Homebrew tap
+    /// Add one or more Homebrew taps
     Tap {
-        /// Tap name (e.g., homebrew/cask)
-        tap: String,
+        /// Tap name(s) (e.g., homebrew/cask)
+        taps: Vec<String>,
+
+        /// Custom git URL to tap from (ssh, https, or file), instead of the default GitHub location. Only valid with a single tap.
+        #[arg(long)]
+        url: Option<String>,
+
+        /// Force a full clone instead of Homebrew's default shallow clone
+        #[arg(long)]
+        full: bool,
     },
 
-    /// Remove a Homebrew tap
+    /// Remove one or more Homebrew taps
     Untap {
-        /// Tap name (e.g., homebrew/cask)
-        tap: String,
+        /// Tap name(s) (e.g., homebrew/cask)
+        taps: Vec<String>,
+
+        /// Only report what would happen, without changing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Continue past failures instead of aborting, and report a summary at the end
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Compare locked package versions against the latest available
+    /// upstream, exiting non-zero if anything is outdated
+    Outdated,
+
+    /// Report formula/cask counts and install status for tracked taps
+    TapInfo {
+        /// Tap name(s) to inspect (all tracked taps if omitted)
+        taps: Vec<String>,
+
+        /// Output machine-readable JSON
+        #[arg(long)]
+        json: bool,
     },
 
     /// Generate shell completion script
@@ -76,6 +181,25 @@ enum Commands {
         /// Shell to generate completions for
         shell: Shell,
     },
+
+    /// Show a diagnostic report of the environment
+    Info {
+        /// Output machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Diagnose environment health: Homebrew state, lock/installed version drift,
+    /// dangling symlinks, orphaned gc entries, and venv liveness
+    Doctor {
+        /// Auto-repair the safe findings (dangling symlinks, missing packages)
+        #[arg(long)]
+        fix: bool,
+
+        /// Output machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 impl Cli {
@@ -85,32 +209,56 @@ impl Cli {
     
     pub fn run(self) -> Result<()> {
         match self.command {
-            Commands::Init => crate::manifest::init(),
-            Commands::Add { packages, impure } => {
+            Commands::Init { arch } => crate::manifest::init(arch),
+            Commands::Add { packages, impure, cask, dry_run, force_isolate } => {
                 for package in &packages {
-                    crate::environment::add(package, impure)?;
+                    if cask {
+                        crate::environment::add_cask(package, dry_run)?;
+                    } else {
+                        crate::environment::add(package, impure, dry_run, force_isolate)?;
+                    }
                 }
                 Ok(())
             }
-            Commands::Remove { packages } => {
+            Commands::Remove { packages, cask, dry_run, force } => {
                 for package in &packages {
-                    crate::environment::remove(package)?;
+                    if cask {
+                        crate::environment::remove_cask(package, dry_run, force)?;
+                    } else {
+                        crate::environment::remove(package, dry_run, force)?;
+                    }
                 }
                 Ok(())
             }
-            Commands::Install => crate::environment::install(),
+            Commands::Install { dry_run, locked, frozen } => {
+                crate::environment::install(dry_run, locked || frozen, frozen)
+            }
             Commands::Shell => crate::shell::enter(),
             Commands::List => crate::manifest::list(),
-            Commands::Sync => crate::environment::sync(),
-            Commands::Gc => crate::environment::gc(),
+            Commands::Sync { dry_run, locked, frozen } => {
+                crate::environment::sync(dry_run, locked || frozen, frozen)
+            }
+            Commands::Gc { dry_run, autoremove } => crate::environment::gc(dry_run, autoremove),
             Commands::Check { quiet } => crate::environment::check(quiet),
-            Commands::Upgrade { package } => crate::environment::upgrade(package.as_deref()),
-            Commands::Tap { tap } => crate::environment::tap(&tap),
-            Commands::Untap { tap } => crate::environment::untap(&tap),
+            Commands::Upgrade { package, latest, to_lock } => {
+                let policy = match (package, latest, to_lock) {
+                    (Some(name), _, true) => crate::environment::UpgradePolicy::PackageToLock(name),
+                    (Some(name), _, false) => crate::environment::UpgradePolicy::Package(name),
+                    (None, true, _) => crate::environment::UpgradePolicy::All,
+                    (None, false, _) => crate::environment::UpgradePolicy::None,
+                };
+                crate::environment::upgrade(policy)
+            }
+            Commands::Outdated => crate::environment::outdated(),
+            Commands::Tap { taps, url, full } => crate::environment::tap(&taps, url, full),
+            Commands::Untap { taps, dry_run, force } => crate::environment::untap(&taps, dry_run, force),
+            Commands::TapInfo { taps, json } => crate::environment::tap_info(&taps, json),
             Commands::Completion { shell } => {
                 Self::generate_completion(shell);
                 Ok(())
             }
+            Commands::Info { json } => crate::info::info(json),
+            Commands::Doctor { fix, json } => crate::doctor::run(fix, json),
         }
     }
 
@@ -0,0 +1,306 @@
+use anyhow::Result;
+use colored::*;
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::environment;
+use crate::homebrew;
+use crate::manifest::{Lock, Manifest};
+
+const PROFILE_BIN: &str = ".macdev/profile/bin";
+const VENV_DIR: &str = ".macdev/venv";
+
+/// How serious a `doctor` finding is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    fn icon(&self) -> colored::ColoredString {
+        match self {
+            Severity::Info => "ℹ".cyan(),
+            Severity::Warning => "⚠".yellow(),
+            Severity::Error => "✗".red(),
+        }
+    }
+}
+
+/// What `doctor --fix` would do to resolve a finding. Kept separate from
+/// `Finding::fix` (a human-readable command) because repairing a dangling
+/// symlink needs a path, not a shell string.
+#[derive(Debug)]
+enum Repair {
+    PruneSymlink(PathBuf),
+    ReinstallMissing,
+}
+
+/// A single diagnostic finding, with a severity and (where one exists) a
+/// concrete command the user could run to fix it themselves
+#[derive(Debug, Serialize)]
+pub struct Finding {
+    pub severity: Severity,
+    pub message: String,
+    pub fix: Option<String>,
+    #[serde(skip)]
+    repair: Option<Repair>,
+}
+
+impl Finding {
+    fn new(severity: Severity, message: impl Into<String>, fix: Option<&str>) -> Self {
+        Finding {
+            severity,
+            message: message.into(),
+            fix: fix.map(str::to_string),
+            repair: None,
+        }
+    }
+
+    fn with_repair(mut self, repair: Repair) -> Self {
+        self.repair = Some(repair);
+        self
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct DoctorReport {
+    pub homebrew_prefix: Option<String>,
+    pub homebrew_version: Option<String>,
+    pub findings: Vec<Finding>,
+}
+
+impl DoctorReport {
+    pub fn is_healthy(&self) -> bool {
+        self.findings.is_empty()
+    }
+}
+
+/// Gather a full diagnostic report of the environment, in the spirit of
+/// `brew doctor`: Homebrew's own health, lock/installed version drift,
+/// dangling profile symlinks, orphaned `gc` entries, and venv liveness.
+pub fn gather() -> Result<DoctorReport> {
+    let mut findings = Vec::new();
+
+    let homebrew_prefix = homebrew::prefix().ok().map(|p| p.display().to_string());
+    let homebrew_version = brew_version();
+
+    if homebrew_prefix.is_none() {
+        findings.push(Finding::new(
+            Severity::Error,
+            "Homebrew is not installed or not on PATH",
+            Some("install Homebrew from https://brew.sh"),
+        ));
+    }
+
+    let local_manifest = Manifest::load().ok();
+    let lock = Lock::load().ok();
+
+    if let Some(lock) = &lock {
+        for (name, locked) in &lock.packages {
+            let installed_version = homebrew::installed_version(&locked.formula).ok().flatten();
+            match installed_version {
+                None => {
+                    findings.push(
+                        Finding::new(
+                            Severity::Error,
+                            format!("{} is locked but not installed via Homebrew", name),
+                            Some("macdev install"),
+                        )
+                        .with_repair(Repair::ReinstallMissing),
+                    );
+                }
+                Some(installed) if installed != locked.version => {
+                    findings.push(Finding::new(
+                        Severity::Warning,
+                        format!(
+                            "{} is locked at {} but {} is installed",
+                            name, locked.version, installed
+                        ),
+                        Some("macdev upgrade --to-lock"),
+                    ));
+                }
+                Some(_) => {}
+            }
+        }
+    }
+
+    findings.extend(dangling_symlinks()?);
+
+    if let Ok(global_manifest) = Manifest::load_global() {
+        for (name, version) in &global_manifest.gc {
+            if !homebrew::is_package_installed(name).unwrap_or(false) {
+                findings.push(Finding::new(
+                    Severity::Info,
+                    format!(
+                        "{} (gc'd at {}) is already gone from Homebrew; its gc entry is orphaned",
+                        name, version
+                    ),
+                    None,
+                ));
+            }
+        }
+    }
+
+    if let Some(finding) = check_venv() {
+        findings.push(finding);
+    }
+
+    if local_manifest.is_none() && lock.is_none() {
+        findings.push(Finding::new(
+            Severity::Info,
+            "No manifest found in this project",
+            Some("macdev init"),
+        ));
+    }
+
+    Ok(DoctorReport {
+        homebrew_prefix,
+        homebrew_version,
+        findings,
+    })
+}
+
+fn brew_version() -> Option<String> {
+    use std::process::Command;
+
+    let output = Command::new("brew").arg("--version").output().ok()?;
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    stdout.lines().next().map(|s| s.to_string())
+}
+
+/// Scan `.macdev/profile/bin` for symlinks whose target no longer exists
+/// (e.g. the formula that provided them was uninstalled outside of macdev)
+fn dangling_symlinks() -> Result<Vec<Finding>> {
+    let mut findings = Vec::new();
+    let bin_dir = PathBuf::from(PROFILE_BIN);
+
+    if !bin_dir.exists() {
+        return Ok(findings);
+    }
+
+    for entry in fs::read_dir(&bin_dir)? {
+        let entry = entry?;
+        let path = entry.path();
+
+        // symlink_metadata never follows the link, so this succeeds even if
+        // the target is gone; a subsequent fs::metadata (which does follow
+        // it) failing means the target no longer exists
+        if path.symlink_metadata().is_ok() && fs::metadata(&path).is_err() {
+            findings.push(
+                Finding::new(
+                    Severity::Warning,
+                    format!("{} is a dangling symlink (target no longer exists)", path.display()),
+                    Some(&format!("rm {}", path.display())),
+                )
+                .with_repair(Repair::PruneSymlink(path.clone())),
+            );
+        }
+    }
+
+    Ok(findings)
+}
+
+/// Whether the Python venv at `.macdev/venv`, if it exists, still points at
+/// a live interpreter
+fn check_venv() -> Option<Finding> {
+    let venv_dir = PathBuf::from(VENV_DIR);
+    if !venv_dir.exists() {
+        return None;
+    }
+
+    let python_bin = venv_dir.join("bin/python3");
+    if fs::metadata(&python_bin).is_err() {
+        return Some(Finding::new(
+            Severity::Warning,
+            format!("{} no longer points at a live interpreter", python_bin.display()),
+            Some("rm -rf .macdev/venv && macdev install"),
+        ));
+    }
+
+    None
+}
+
+/// Apply the safe auto-repairs: prune dangling symlinks, and re-run
+/// `install` once if anything is missing from Homebrew
+fn apply_repairs(findings: &[Finding]) -> Result<(usize, usize)> {
+    let mut pruned = 0;
+    let mut needs_reinstall = false;
+
+    for finding in findings {
+        match &finding.repair {
+            Some(Repair::PruneSymlink(path)) => {
+                if fs::remove_file(path).is_ok() {
+                    pruned += 1;
+                }
+            }
+            Some(Repair::ReinstallMissing) => needs_reinstall = true,
+            None => {}
+        }
+    }
+
+    if needs_reinstall {
+        environment::install(false, false, false)?;
+    }
+
+    Ok((pruned, needs_reinstall as usize))
+}
+
+/// Run `doctor`: report findings, optionally auto-repairing the safe ones
+pub fn run(fix: bool, json: bool) -> Result<()> {
+    let mut report = gather()?;
+
+    if fix {
+        let (pruned, reinstalled) = apply_repairs(&report.findings)?;
+        if pruned > 0 || reinstalled > 0 {
+            // Re-gather so the printed report reflects the repairs just made
+            report = gather()?;
+        }
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    println!("{}", "macdev doctor".cyan().bold());
+    println!();
+
+    match &report.homebrew_prefix {
+        Some(prefix) => println!("  Homebrew prefix: {}", prefix),
+        None => println!("  Homebrew prefix: {}", "unknown".red()),
+    }
+    if let Some(version) = &report.homebrew_version {
+        println!("  {}", version.bright_black());
+    }
+
+    println!();
+
+    if report.is_healthy() {
+        println!("{} Environment looks healthy", "✓".green());
+        return Ok(());
+    }
+
+    for finding in &report.findings {
+        println!("  {} {}", finding.severity.icon(), finding.message);
+        if let Some(fix) = &finding.fix {
+            println!("      {} {}", "→".blue(), fix);
+        }
+    }
+
+    println!();
+    let errors = report.findings.iter().filter(|f| f.severity == Severity::Error).count();
+    let warnings = report.findings.iter().filter(|f| f.severity == Severity::Warning).count();
+    println!(
+        "{} {} error(s), {} warning(s){}",
+        if errors > 0 { "✗".red() } else { "⚠".yellow() },
+        errors,
+        warnings,
+        if fix { "" } else { " (run with --fix to auto-repair what's safe)" }
+    );
+
+    Ok(())
+}
@@ -1,11 +1,13 @@
 use anyhow::{Context, Result};
 use colored::*;
+use serde::Serialize;
 use std::fs;
 use std::os::unix::fs as unix_fs;
 use std::path::{Path, PathBuf};
 
 use crate::homebrew;
 use crate::manifest::Manifest;
+use crate::transaction::Transaction;
 
 const PROFILE_DIR: &str = ".macdev/profile";
 
@@ -20,8 +22,16 @@ fn parse_package_spec(spec: &str) -> (String, Option<String>) {
     }
 }
 
+/// Whether an impure (system-wide) install already satisfies a pure
+/// package request, so isolating it into the project profile would just
+/// duplicate an install already on `PATH`.
+fn satisfied_by_impure(global_manifest: &Manifest, package: &str, name: &str) -> bool {
+    let covered = global_manifest.impure.contains_key(package) || global_manifest.impure.contains_key(name);
+    covered && homebrew::is_package_installed(package).unwrap_or(false)
+}
+
 /// Add a package to the environment
-pub fn add(package_spec: &str, impure: bool) -> Result<()> {
+pub fn add(package_spec: &str, impure: bool, dry_run: bool, force_isolate: bool) -> Result<()> {
     // Check Homebrew is installed
     if !homebrew::is_installed() {
         anyhow::bail!("Homebrew is not installed. Install it from https://brew.sh");
@@ -37,34 +47,82 @@ pub fn add(package_spec: &str, impure: bool) -> Result<()> {
     let mut global_manifest = Manifest::load_global()?;
     let name = package.split('@').next().unwrap().to_string();
 
+    if dry_run {
+        if !impure && !force_isolate && satisfied_by_impure(&global_manifest, &package, &name) {
+            println!("  {} {} (pure, satisfied by system-wide install)", "-".yellow(), package);
+            return Ok(());
+        }
+
+        let already_installed = homebrew::is_package_installed(&package).unwrap_or(false);
+        let status = if already_installed { "already installed" } else { "would install" };
+        println!(
+            "  {} {} ({}, {})",
+            "-".yellow(),
+            package,
+            if impure { "impure" } else { "pure" },
+            status
+        );
+        if global_manifest.gc.contains_key(&name) {
+            println!("    Would restore from gc");
+        }
+        return Ok(());
+    }
+
     // Check if package is in gc section and remove it
     let was_in_gc = global_manifest.gc.remove(&name).is_some();
     if was_in_gc {
         println!("  Package was in gc, restoring...");
     }
 
+    let mut tx = Transaction::new();
+    let already_installed = homebrew::is_package_installed(&package).unwrap_or(false);
+
     if impure {
         // Impure: install normally (with linking) and track in global manifest
         println!("{} {} (impure)", "Adding".green(), package);
         homebrew::ensure_package(&package, true)?; // link=true
+        if !already_installed {
+            tx.record_install(package.clone());
+        }
 
         global_manifest.add_impure(name);
         global_manifest.save_global()?;
 
         let path = Manifest::global_manifest_display_path()?;
         println!("{} Package available system-wide (saved to {})", "✓".green(), path);
+    } else if !force_isolate && satisfied_by_impure(&global_manifest, &package, &name) {
+        // Pure, but a system-wide (impure) install already covers this spec:
+        // reuse it instead of installing and symlinking a redundant copy
+        println!("{} {} (pure, satisfied by system-wide install)", "Adding".green(), package);
+
+        let mut local_manifest = Manifest::load()?;
+        let ver = version.unwrap_or_else(|| "*".to_string());
+        local_manifest.add_package(name, ver);
+        local_manifest.save()?;
+
+        println!(
+            "{} Reusing system-wide install (pass --force-isolate for a project-local copy)",
+            "✓".green()
+        );
     } else {
         // Pure: install with --no-link and track in both local and global manifests
         println!("{} {} (pure)", "Adding".green(), package);
         let brew_path = homebrew::ensure_package(&package, false)?; // link=false
+        if !already_installed {
+            tx.record_install(package.clone());
+        }
+
+        // Track in local manifest (this project needs it)
+        let mut local_manifest = Manifest::load()?;
+
+        let variant = crate::brew_variant::BrewVariant::select(local_manifest.arch.as_deref());
+        homebrew::warn_if_wrong_variant(&brew_path, &variant);
 
         // Create symlinks
-        create_symlinks(&package, &brew_path)?;
+        create_symlinks(&package, &brew_path, false, false, &mut Vec::new())?;
 
         let ver = version.unwrap_or_else(|| "*".to_string());
 
-        // Track in local manifest (this project needs it)
-        let mut local_manifest = Manifest::load()?;
         local_manifest.add_package(name.clone(), ver.clone());
         local_manifest.save()?;
 
@@ -93,19 +151,111 @@ pub fn add(package_spec: &str, impure: bool) -> Result<()> {
 
             if !in_packages && !in_impure && !in_gc {
                 println!("    Unlinking {} (dependency)", dep);
-                let _ = homebrew::unlink_package(&dep); // Ignore errors
+                if homebrew::unlink_package(&dep).is_ok() {
+                    tx.record_unlink(dep);
+                }
             }
         }
     }
 
-    // Generate lock file
+    // Everything succeeded: commit so Drop won't roll back, then persist the lock
+    tx.success();
     let _ = crate::manifest::generate_lock(); // Ignore errors
 
     Ok(())
 }
 
+/// Add a cask (GUI application) to the environment. Casks always behave as
+/// impure/system-wide installs since they can't be symlinked into the
+/// project profile.
+pub fn add_cask(name: &str, dry_run: bool) -> Result<()> {
+    use colored::*;
+
+    if !homebrew::is_installed() {
+        anyhow::bail!("Homebrew is not installed. Install it from https://brew.sh");
+    }
+
+    let mut global_manifest = Manifest::load_global()?;
+
+    if dry_run {
+        let already_installed = homebrew::is_cask_installed(name).unwrap_or(false);
+        let status = if already_installed { "already installed" } else { "would install" };
+        println!("  {} {} (cask, {})", "-".yellow(), name, status);
+        if global_manifest.gc.contains_key(name) {
+            println!("    Would restore from gc");
+        }
+        return Ok(());
+    }
+
+    let was_in_gc = global_manifest.gc.remove(name).is_some();
+    if was_in_gc {
+        println!("  Cask was in gc, restoring...");
+    }
+
+    println!("{} {} (cask)", "Adding".green(), name);
+
+    let mut tx = Transaction::new();
+    let already_installed = homebrew::is_cask_installed(name).unwrap_or(false);
+    homebrew::ensure_cask(name)?;
+    if !already_installed {
+        tx.record_cask_install(name.to_string());
+    }
+
+    global_manifest.add_cask(name.to_string());
+    global_manifest.save_global()?;
+    tx.success();
+
+    let path = Manifest::global_manifest_display_path()?;
+    println!("{} Cask available system-wide (saved to {})", "✓".green(), path);
+
+    Ok(())
+}
+
+/// Print a summary of failures collected under `force` mode, in the spirit
+/// of the uninstaller convention of `:dry_run`/`:force`/`:quiet` options
+fn report_failures(failures: &[String]) {
+    if failures.is_empty() {
+        return;
+    }
+
+    println!();
+    println!("{} {} failure(s) occurred:", "⚠".yellow(), failures.len());
+    for failure in failures {
+        println!("  {} {}", "✗".red(), failure);
+    }
+}
+
+/// Remove a cask from the environment
+pub fn remove_cask(name: &str, dry_run: bool, force: bool) -> Result<()> {
+    use colored::*;
+
+    let mut global_manifest = Manifest::load_global()?;
+
+    if !global_manifest.casks.contains_key(name) {
+        anyhow::bail!("Cask '{}' is not tracked globally", name);
+    }
+
+    if dry_run {
+        println!("  {} {} would be removed (cask, moved to gc)", "-".yellow(), name);
+        return Ok(());
+    }
+
+    println!("{} {} (cask)", "Removing".yellow(), name);
+
+    global_manifest.remove_cask(name);
+    global_manifest.gc.insert(name.to_string(), "*".to_string());
+
+    match global_manifest.save_global() {
+        Ok(()) => println!("{} Removed {} (cask, moved to gc)", "✓".green(), name),
+        Err(e) if force => report_failures(&[format!("failed to save global manifest: {}", e)]),
+        Err(e) => return Err(e),
+    }
+
+    Ok(())
+}
+
 /// Remove a package from the environment
-pub fn remove(package: &str) -> Result<()> {
+pub fn remove(package: &str, dry_run: bool, force: bool) -> Result<()> {
     use colored::*;
 
     // Extract base name (e.g., "python@3.12" -> "python")
@@ -142,8 +292,20 @@ pub fn remove(package: &str) -> Result<()> {
         anyhow::bail!("Package '{}' is not tracked globally", package);
     }
 
+    if dry_run {
+        println!("  {} {} would be removed ({})", "-".yellow(), package, if is_impure { "impure" } else { "pure" });
+        if !is_impure && local_manifest.as_ref().is_some_and(|m| {
+            m.packages.contains_key(package) || m.packages.contains_key(package_base)
+        }) {
+            println!("    Would remove from local project manifest and rebuild profile");
+        }
+        return Ok(());
+    }
+
     println!("{} {} from environment", "Removing".yellow(), package);
 
+    let mut failures = Vec::new();
+
     if is_impure {
         // Impure package: move to gc section in global manifest
         // Try both full name and base name
@@ -172,8 +334,10 @@ pub fn remove(package: &str) -> Result<()> {
             local.remove_package(pkg_key);
             local.save()?;
 
-            // Rebuild profile (removes symlinks for pure packages)
-            rebuild_profile(&local)?;
+            // Rebuild profile (removes symlinks for pure packages). dry_run
+            // is always false here; the dry-run preview above already
+            // returned before any of this ran.
+            failures.extend(rebuild_profile(&local, false, force)?);
             println!("  Removed from local project manifest");
         }
 
@@ -198,16 +362,39 @@ pub fn remove(package: &str) -> Result<()> {
     // Update lock file
     let _ = crate::manifest::generate_lock(); // Ignore errors
 
+    report_failures(&failures);
+
     Ok(())
 }
 
 /// Sync packages from manifest(s)
-pub fn sync() -> Result<()> {
+pub fn sync(dry_run: bool, locked: bool, frozen: bool) -> Result<()> {
     use colored::*;
+    use crate::manifest::Lock;
 
     let local_manifest = Manifest::load().ok();
     let global_manifest = Manifest::load_global()?;
 
+    if locked {
+        match &local_manifest {
+            Some(local) if !local.packages.is_empty() => {
+                if !Lock::exists() {
+                    anyhow::bail!(
+                        "--locked requires an existing lock file, but none was found. Run \
+                         'macdev sync' without --locked to generate one."
+                    );
+                }
+                if !Lock::load()?.is_consistent_with(local) {
+                    anyhow::bail!(
+                        "Manifest and lock file are out of sync. Run 'macdev sync' without \
+                         --locked to regenerate the lock."
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
     println!("{}", "Syncing packages from manifest(s)...".cyan().bold());
     println!();
 
@@ -217,14 +404,26 @@ pub fn sync() -> Result<()> {
     if !global_manifest.taps.is_empty() {
         println!("{}", "Syncing taps from global manifest:".magenta());
 
-        for tap_name in global_manifest.taps.keys() {
+        for (tap_name, entry) in &global_manifest.taps {
             match homebrew::is_tap_tapped(tap_name) {
                 Ok(true) => {
                     println!("  {} {} (already tapped)", "✓".green(), tap_name);
                 }
                 Ok(false) | Err(_) => {
-                    println!("  {} {}", "→".blue(), tap_name);
-                    homebrew::tap(tap_name)?;
+                    if frozen {
+                        anyhow::bail!(
+                            "{} is not tapped and --frozen forbids fetching it from Homebrew. \
+                             Run without --frozen to sync it.",
+                            tap_name
+                        );
+                    }
+
+                    if dry_run {
+                        println!("  {} {} (would tap)", "-".yellow(), tap_name);
+                    } else {
+                        println!("  {} {}", "→".blue(), tap_name);
+                        homebrew::tap(tap_name, entry.url.as_deref(), entry.full)?;
+                    }
                     synced_count += 1;
                 }
             }
@@ -232,6 +431,42 @@ pub fn sync() -> Result<()> {
         println!();
     }
 
+    // Untap anything currently tapped that isn't listed in the global
+    // manifest (e.g. the manifest file was hand-edited). This is what makes
+    // sync a true reconciliation rather than a one-way "add what's missing"
+    // pass: a fresh machine converges to exactly what's declared, not a
+    // superset of it. Homebrew's own core/cask taps aren't something macdev
+    // tracks, so they're left alone even though they're never in the
+    // manifest.
+    const DEFAULT_TAPS: &[&str] = &["homebrew/core", "homebrew/cask"];
+    let drifted_taps: Vec<String> = homebrew::installed_taps()?
+        .into_iter()
+        .filter(|t| !DEFAULT_TAPS.contains(&t.as_str()) && !global_manifest.taps.contains_key(t))
+        .collect();
+
+    if !drifted_taps.is_empty() {
+        println!("{}", "Untapping taps removed from the manifest:".magenta());
+
+        for tap_name in &drifted_taps {
+            if frozen {
+                anyhow::bail!(
+                    "{} is tapped but not in the manifest, and --frozen forbids reconciling \
+                     it. Run without --frozen to untap it.",
+                    tap_name
+                );
+            }
+
+            if dry_run {
+                println!("  {} {} (would untap)", "-".yellow(), tap_name);
+            } else {
+                println!("  {} {}", "←".red(), tap_name);
+                homebrew::untap(tap_name)?;
+            }
+            synced_count += 1;
+        }
+        println!();
+    }
+
     // If in a project, sync pure packages from local manifest
     if let Some(local) = &local_manifest && !local.packages.is_empty() {
         println!("{}", "Syncing pure packages from local manifest:".green());
@@ -245,13 +480,33 @@ pub fn sync() -> Result<()> {
                     format!("{}@{}", name, version)
                 };
 
-                println!("  {} {}", "→".blue(), spec);
-                add(&spec, false)?;
+                if frozen && !homebrew::is_package_installed(&spec).unwrap_or(false) {
+                    anyhow::bail!(
+                        "{} is not installed and --frozen forbids fetching it from Homebrew. Run \
+                         without --frozen to sync it.",
+                        spec
+                    );
+                }
+
+                if !dry_run {
+                    println!("  {} {}", "→".blue(), spec);
+                }
+                add(&spec, false, dry_run, false)?;
                 synced_count += 1;
             } else {
                 println!("  {} {} (already installed)", "✓".green(), name);
             }
         }
+
+        // Rebuild symlinks for the whole package set so the profile always
+        // matches the manifest exactly, not just whatever was missing.
+        if dry_run {
+            println!("  {} profile would be rebuilt to match the manifest", "-".yellow());
+        } else {
+            let rebuild_failures = rebuild_profile(local, false, false)?;
+            report_failures(&rebuild_failures);
+        }
+
         println!();
     }
 
@@ -266,19 +521,60 @@ pub fn sync() -> Result<()> {
                     println!("  {} {} (already installed)", "✓".green(), name);
                 }
                 Ok(false) | Err(_) => {
-                    println!("  {} {}", "→".blue(), name);
-                    add(name, true)?;
+                    if frozen {
+                        anyhow::bail!(
+                            "{} is not installed and --frozen forbids fetching it from \
+                             Homebrew. Run without --frozen to sync it.",
+                            name
+                        );
+                    }
+
+                    if !dry_run {
+                        println!("  {} {}", "→".blue(), name);
+                    }
+                    add(name, true, dry_run, false)?;
                     synced_count += 1;
                 }
             }
         }
     }
 
+    // Sync casks from global manifest
+    if !global_manifest.casks.is_empty() {
+        println!("{}", "Syncing casks from global manifest:".magenta());
+
+        for name in global_manifest.casks.keys() {
+            match homebrew::is_cask_installed(name) {
+                Ok(true) => {
+                    println!("  {} {} (already installed)", "✓".green(), name);
+                }
+                Ok(false) | Err(_) => {
+                    if frozen {
+                        anyhow::bail!(
+                            "{} is not installed and --frozen forbids fetching it from \
+                             Homebrew. Run without --frozen to sync it.",
+                            name
+                        );
+                    }
+
+                    if !dry_run {
+                        println!("  {} {}", "→".blue(), name);
+                    }
+                    add_cask(name, dry_run)?;
+                    synced_count += 1;
+                }
+            }
+        }
+        println!();
+    }
+
     println!();
-    if synced_count > 0 {
-        println!("{} Synced {} item(s)", "✓".green(), synced_count);
-    } else {
+    if synced_count == 0 {
         println!("{}", "All items already synced".yellow());
+    } else if dry_run {
+        println!("{} {} item(s) would be synced", "✓".green(), synced_count);
+    } else {
+        println!("{} Synced {} item(s)", "✓".green(), synced_count);
     }
 
     Ok(())
@@ -329,29 +625,124 @@ pub fn check(quiet: bool) -> Result<()> {
     Ok(())
 }
 
-/// Garbage collect packages marked for removal
-pub fn gc() -> Result<()> {
-    use colored::*;
+/// Walk `homebrew::package_deps` transitively from every manifest root,
+/// recording the graph into `lock.dependencies` and returning the set of
+/// base package names reachable as a dependency of some root.
+fn dependency_closure(roots: &[String], lock: &mut crate::manifest::Lock) -> std::collections::HashSet<String> {
+    let mut closure = std::collections::HashSet::new();
+    let mut to_visit: Vec<String> = roots.to_vec();
+    let mut visited: std::collections::HashSet<String> = std::collections::HashSet::new();
+
+    while let Some(pkg) = to_visit.pop() {
+        if !visited.insert(pkg.clone()) {
+            continue;
+        }
 
-    let mut global_manifest = Manifest::load_global()?;
+        let deps = homebrew::package_deps(&pkg).unwrap_or_default();
+        for dep in deps {
+            let dep_base = dep.split('@').next().unwrap().to_string();
+            closure.insert(dep_base);
 
-    if global_manifest.gc.is_empty() {
-        println!("{}", "No packages to garbage collect".yellow());
-        return Ok(());
+            if let Ok(info) = homebrew::package_info(&dep) {
+                lock.add_dependency(pkg.clone(), dep.clone(), info.version, info.formula, None);
+            }
+
+            to_visit.push(dep);
+        }
     }
 
+    closure
+}
+
+/// Garbage collect packages no longer reachable from the manifest
+///
+/// By default this only removes packages explicitly moved into the `gc`
+/// table (e.g. by `macdev remove`). Pass `autoremove` to additionally walk
+/// the full dependency graph, reference-count every installed formula
+/// against the manifest's roots (packages + impure + casks), and remove any
+/// dependency-only formula whose refcount has dropped to zero — mirroring
+/// `brew autoremove`, but scoped to macdev-managed state.
+pub fn gc(dry_run: bool, autoremove: bool) -> Result<()> {
+    use colored::*;
+    use crate::manifest::Lock;
+
+    let mut global_manifest = Manifest::load_global()?;
+    let local_manifest = Manifest::load().ok();
+
     println!("{}", "Garbage collecting unused packages...".cyan().bold());
     println!();
 
-    let mut to_remove = Vec::new();
+    // Anything explicitly moved into the gc table is always a target
+    let mut targets: Vec<String> = global_manifest.gc.keys().cloned().collect();
 
-    for name in global_manifest.gc.keys() {
-        println!("  {} {}", "Uninstalling".red(), name);
+    let mut lock = Lock::load().unwrap_or_default();
 
-        match homebrew::uninstall_package(name) {
-            Ok(_) => {
-                to_remove.push(name.clone());
+    if autoremove {
+        // Every top-level manifest entry is a root of the dependency closure
+        let mut roots: Vec<String> = Vec::new();
+        if let Some(local) = &local_manifest {
+            roots.extend(local.packages.keys().cloned());
+        }
+        roots.extend(global_manifest.packages.keys().cloned());
+        roots.extend(global_manifest.impure.keys().cloned());
+
+        let closure = dependency_closure(&roots, &mut lock);
+
+        // Anything Homebrew has installed that isn't a root, isn't reachable
+        // from one (refcount zero), and isn't already queued is an orphaned
+        // dependency macdev pulled in on a root's behalf
+        for formula in homebrew::installed_formulae().unwrap_or_default() {
+            let base = formula.split('@').next().unwrap().to_string();
+            let is_root = roots.iter().any(|r| *r == formula || *r == base);
+            let is_impure = global_manifest.impure.contains_key(&formula)
+                || global_manifest.impure.contains_key(&base);
+            let in_closure = closure.contains(&formula) || closure.contains(&base);
+
+            if !is_root && !is_impure && !in_closure && !targets.contains(&formula) {
+                targets.push(formula);
             }
+        }
+
+        // Casks have no dependency graph to walk, but any installed cask that
+        // isn't a tracked manifest entry is equally an orphan
+        for cask in homebrew::installed_casks().unwrap_or_default() {
+            if !global_manifest.casks.contains_key(&cask) && !targets.contains(&cask) {
+                targets.push(cask);
+            }
+        }
+    }
+
+    if targets.is_empty() {
+        println!("{}", "Nothing to garbage collect".yellow());
+        return Ok(());
+    }
+
+    if dry_run {
+        println!("{}", "The following packages would be removed:".yellow());
+        for name in &targets {
+            println!("  {} {}", "-".red().bold(), name);
+        }
+        return Ok(());
+    }
+
+    let mut removed = Vec::new();
+    for name in &targets {
+        println!("  {} {}", "Uninstalling".red(), name);
+
+        // Casks and formulae are uninstalled differently; a name tracked in
+        // the cask table (or still present as an installed cask) goes
+        // through `brew uninstall --cask`.
+        let is_cask = global_manifest.casks.contains_key(name)
+            || homebrew::is_cask_installed(name).unwrap_or(false);
+
+        let result = if is_cask {
+            homebrew::uninstall_cask(name)
+        } else {
+            homebrew::uninstall_package(name)
+        };
+
+        match result {
+            Ok(_) => removed.push(name.clone()),
             Err(e) => {
                 println!("    {} Failed to uninstall: {}", "⚠".yellow(), e);
                 println!("    Keeping in gc for next run");
@@ -359,16 +750,18 @@ pub fn gc() -> Result<()> {
         }
     }
 
-    // Remove successfully uninstalled packages from gc
-    for name in &to_remove {
+    for name in &removed {
         global_manifest.gc.remove(name);
     }
 
     global_manifest.save_global()?;
+    if autoremove {
+        lock.save()?;
+    }
 
     println!();
-    if !to_remove.is_empty() {
-        println!("{} Uninstalled {} package(s)", "✓".green(), to_remove.len());
+    if !removed.is_empty() {
+        println!("{} Uninstalled {} package(s)", "✓".green(), removed.len());
     }
 
     // Run brew cleanup
@@ -384,163 +777,366 @@ pub fn gc() -> Result<()> {
     Ok(())
 }
 
-/// Upgrade packages
-pub fn upgrade(package: Option<&str>) -> Result<()> {
+/// Report, for every package in the local manifest, whether a newer version
+/// is available upstream. Distinguishes "latest matching the manifest
+/// constraint" (e.g. the newest `3.11.x` for a package pinned to `@3.11`)
+/// from "latest overall" (e.g. `3.13` existing too), the way dependency
+/// freshness tools report a "wanted" version alongside "latest". Returns an
+/// error (and the process exits non-zero) if anything is outdated, so this
+/// can gate CI.
+pub fn outdated() -> Result<()> {
+    use crate::manifest::Lock;
     use colored::*;
-    use std::process::Command;
 
-    // Load manifests to know which packages are managed
-    let local_manifest = Manifest::load().ok();
-    let global_manifest = Manifest::load_global()?;
+    let local_manifest = Manifest::load()?;
+
+    if local_manifest.packages.is_empty() {
+        println!("No packages in manifest");
+        return Ok(());
+    }
 
-    if let Some(pkg) = package {
-        // Upgrade specific package
-        println!("{} {}", "Upgrading".cyan(), pkg);
+    let lock = Lock::load().ok();
 
-        // Check if package is managed
-        let pkg_base = pkg.split('@').next().unwrap();
-        let is_pure = local_manifest.as_ref().is_some_and(|m| m.packages.contains_key(pkg_base));
-        let is_impure = global_manifest.impure.contains_key(pkg_base);
+    let mut names: Vec<&String> = local_manifest.packages.keys().collect();
+    names.sort();
 
-        if !is_pure && !is_impure {
-            anyhow::bail!("Package '{}' is not managed by macdev", pkg);
-        }
+    println!("PACKAGE              LOCKED          LATEST          STATUS");
 
-        // Run brew upgrade
-        let status = Command::new("brew")
-            .args(["upgrade", pkg])
-            .status()
-            .context("Failed to run 'brew upgrade'")?;
+    let mut any_outdated = false;
 
-        if !status.success() {
-            anyhow::bail!("Failed to upgrade {}", pkg);
-        }
+    for name in names {
+        let constraint = &local_manifest.packages[name];
 
-        // Rebuild profile if pure package
-        if is_pure {
-            println!("  Rebuilding profile...");
-            if let Some(local) = local_manifest {
-                rebuild_profile(&local)?;
-            }
+        let locked_version = lock
+            .as_ref()
+            .and_then(|l| l.packages.get(name))
+            .map(|pkg| pkg.version.clone());
 
-            // Check if Python was upgraded
-            if pkg_base == "python" || pkg.starts_with("python@") {
-                println!();
-                println!("  {} Python was upgraded. You may want to recreate the venv:", "ℹ".cyan());
-                println!("    rm -rf .macdev/venv");
-                println!("    macdev install");
+        let candidates = homebrew::available_versions(name).unwrap_or_default();
+        let latest_matching = crate::version::resolve(constraint, &candidates).ok();
+        let latest_overall = crate::version::latest(&candidates);
+
+        let status = match (&locked_version, &latest_matching) {
+            (None, _) => "not locked".yellow().to_string(),
+            (Some(_), None) => "unknown".red().to_string(),
+            (Some(locked), Some(matching)) if locked != matching => {
+                any_outdated = true;
+                "compatible upgrade".yellow().to_string()
             }
+            (Some(_), Some(matching)) => match &latest_overall {
+                Some(overall) if overall != matching => {
+                    any_outdated = true;
+                    "major bump".blue().to_string()
+                }
+                _ => "up to date".green().to_string(),
+            },
+        };
+
+        println!(
+            "{:<20} {:<15} {:<15} {}",
+            name,
+            locked_version.as_deref().unwrap_or("-"),
+            latest_matching.as_deref().unwrap_or("-"),
+            status
+        );
+
+        if let (Some(matching), Some(overall)) = (&latest_matching, &latest_overall)
+            && overall != matching
+        {
+            println!(
+                "  {} {} available outside the {} series",
+                "→".blue(),
+                overall,
+                constraint
+            );
         }
+    }
+
+    if any_outdated {
+        anyhow::bail!("One or more packages are outdated");
+    }
+
+    Ok(())
+}
 
-        println!("{} Upgraded {}", "✓".green(), pkg);
+/// Which packages `upgrade` should touch, modeled on uv's upgrade policy.
+pub enum UpgradePolicy {
+    /// Upgrade nothing; only pure packages whose installed version differs
+    /// from the lock file are resynced (see `resync_to_lock`).
+    None,
+    /// Ignore the lock file and upgrade every managed package to the
+    /// newest version Homebrew offers.
+    All,
+    /// Upgrade only the named package, to the newest version Homebrew offers.
+    Package(String),
+    /// Resync only the named package against the lock file (see
+    /// `resync_to_lock`), without upgrading it to anything newer.
+    PackageToLock(String),
+}
 
-        // Generate lock file
-        let _ = crate::manifest::generate_lock(); // Ignore errors
+/// Upgrade a single package in place, respecting its manifest pin.
+/// Returns `Ok(true)` if a newer version was actually installed.
+fn upgrade_package(name: &str, pinned_version: &str) -> Result<bool> {
+    use colored::*;
+    use std::process::Command;
+
+    // Rebuilding the spec with the pin (e.g. "python@3.11") keeps the upgrade
+    // within that series, since it's a distinct brew formula; "*" upgrades freely.
+    let spec = if pinned_version == "*" {
+        name.to_string()
     } else {
-        // Upgrade all managed packages
-        println!("{}", "Upgrading all managed packages...".cyan().bold());
-        println!();
+        format!("{}@{}", name, pinned_version)
+    };
 
-        let mut upgraded_count = 0;
-        let mut python_upgraded = false;
+    if !homebrew::is_package_installed(&spec).unwrap_or(false) {
+        println!("  {} {} (not installed, skipping)", "⚠".yellow(), spec);
+        return Ok(false);
+    }
 
-        // Upgrade pure packages
-        if let Some(local) = &local_manifest
-            && !local.packages.is_empty() {
-            println!("{}", "Upgrading pure packages:".green());
-            for (name, version) in &local.packages {
-                let spec = if version == "*" {
-                    name.clone()
-                } else {
-                    format!("{}@{}", name, version)
-                };
+    let (installed, available) = match homebrew::outdated_versions(&spec)? {
+        Some(versions) => versions,
+        None => {
+            println!("  {} {} (already up to date)", "✓".green(), spec);
+            return Ok(false);
+        }
+    };
+
+    // Trust our own comparison over brew's outdated verdict, since the point
+    // of a version-aware upgrade is to only ever move strictly forward.
+    if !crate::version::is_newer(&installed, &available) {
+        println!("  {} {} (already up to date)", "✓".green(), spec);
+        return Ok(false);
+    }
+
+    println!("  {} {}", "→".blue(), spec);
+    let status = Command::new("brew")
+        .args(["upgrade", &spec])
+        .status()
+        .context("Failed to run 'brew upgrade'")?;
+
+    if !status.success() {
+        anyhow::bail!("Failed to upgrade {}", spec);
+    }
+
+    println!("    {} {} -> {}", "~".yellow(), installed, available);
+
+    Ok(true)
+}
+
+/// Resync pure packages whose installed version differs from the lock
+/// file, without upgrading anything. Each such package's locked formula
+/// goes through `ensure_package`, which installs Homebrew's current version
+/// only if the formula is missing, and is then re-linked into the profile;
+/// an installed version that differs from the locked one is left as it is.
+/// When `only` is set, resync just that one package instead of every pure
+/// package in the manifest.
+fn resync_to_lock(local_manifest: &Manifest, only: Option<&str>) -> Result<usize> {
+    use crate::manifest::Lock;
+    use colored::*;
+
+    if !Lock::exists() {
+        println!(
+            "{}",
+            "No lock file found; nothing to resync. Run 'macdev install' to generate one, \
+             or 'macdev upgrade --latest' to upgrade freely."
+                .yellow()
+        );
+        return Ok(0);
+    }
+
+    let lock = Lock::load()?;
+    let mut resynced = 0;
+
+    for name in local_manifest.packages.keys() {
+        if let Some(only) = only
+            && name != only
+        {
+            continue;
+        }
+
+        let Some(locked) = lock.packages.get(name) else {
+            continue;
+        };
+
+        let installed = homebrew::installed_version(&locked.formula).ok().flatten();
+        if installed.as_deref() == Some(locked.version.as_str()) {
+            println!("  {} {} @ {} (matches lock)", "✓".green(), name, locked.version);
+            continue;
+        }
+
+        println!("  {} {} -> {} (resyncing to lock)", "→".blue(), name, locked.version);
+        let brew_path = homebrew::ensure_package(&locked.formula, false)?;
+        create_symlinks(&locked.formula, &brew_path, false, false, &mut Vec::new())?;
+        resynced += 1;
+    }
+
+    Ok(resynced)
+}
+
+/// Upgrade packages, re-resolving against Homebrew and diffing against the lockfile
+pub fn upgrade(policy: UpgradePolicy) -> Result<()> {
+    use colored::*;
+
+    let local_manifest = Manifest::load().ok();
+    let global_manifest = Manifest::load_global()?;
 
-                println!("  {} {}", "→".blue(), spec);
-                let output = Command::new("brew")
-                    .args(["upgrade", &spec])
-                    .output();
+    let mut upgraded_count = 0;
+    let mut python_upgraded = false;
 
-                if let Ok(output) = output {
-                    let stderr = String::from_utf8_lossy(&output.stderr);
-                    // Check if actually upgraded (not "already installed")
-                    if output.status.success() && !stderr.contains("already installed") {
+    match &policy {
+        UpgradePolicy::Package(pkg) => {
+            let pkg_base = pkg.split('@').next().unwrap();
+            let is_pure = local_manifest.as_ref().is_some_and(|m| m.packages.contains_key(pkg_base));
+            let is_impure = global_manifest.impure.contains_key(pkg_base);
+
+            if !is_pure && !is_impure {
+                anyhow::bail!("Package '{}' is not managed by macdev", pkg);
+            }
+
+            println!("{} {}", "Upgrading".cyan(), pkg);
+
+            let pinned = local_manifest
+                .as_ref()
+                .and_then(|m| m.packages.get(pkg_base))
+                .cloned()
+                .unwrap_or_else(|| "*".to_string());
+
+            if upgrade_package(pkg_base, &pinned)? {
+                upgraded_count += 1;
+                if pkg_base == "python" {
+                    python_upgraded = true;
+                }
+            }
+        }
+        UpgradePolicy::All => {
+            // Upgrade every package managed by the manifest
+            println!("{}", "Upgrading all managed packages to latest...".cyan().bold());
+            println!();
+
+            if let Some(local) = &local_manifest
+                && !local.packages.is_empty() {
+                println!("{}", "Upgrading pure packages:".green());
+                for (name, version) in &local.packages {
+                    if upgrade_package(name, version)? {
                         upgraded_count += 1;
-                        if name == "python" || spec.starts_with("python@") {
+                        if name == "python" {
                             python_upgraded = true;
                         }
                     }
                 }
+                println!();
             }
-            println!();
-        }
-
-        // Upgrade impure packages
-        if !global_manifest.impure.is_empty() {
-            println!("{}", "Upgrading impure packages:".cyan());
-            for name in global_manifest.impure.keys() {
-                println!("  {} {}", "→".blue(), name);
-                let output = Command::new("brew")
-                    .args(["upgrade", name])
-                    .output();
 
-                if let Ok(output) = output {
-                    let stderr = String::from_utf8_lossy(&output.stderr);
-                    // Check if actually upgraded (not "already installed")
-                    if output.status.success() && !stderr.contains("already installed") {
+            if !global_manifest.impure.is_empty() {
+                println!("{}", "Upgrading impure packages:".cyan());
+                for name in global_manifest.impure.keys() {
+                    if upgrade_package(name, "*")? {
                         upgraded_count += 1;
                     }
                 }
+                println!();
             }
-            println!();
         }
+        UpgradePolicy::None => {
+            println!("{}", "Resyncing pure packages to the lock file...".cyan().bold());
+            println!();
 
-        // Rebuild profile if any pure packages were upgraded
-        if let Some(local) = local_manifest
-            && !local.packages.is_empty() {
-            println!("Rebuilding profile...");
-            rebuild_profile(&local)?;
+            if let Some(local) = &local_manifest
+                && !local.packages.is_empty() {
+                upgraded_count += resync_to_lock(local, None)?;
+            } else {
+                println!("{}", "No local manifest; nothing to resync".yellow());
+            }
         }
+        UpgradePolicy::PackageToLock(pkg) => {
+            let pkg_base = pkg.split('@').next().unwrap();
+            let is_pure = local_manifest.as_ref().is_some_and(|m| m.packages.contains_key(pkg_base));
+
+            if !is_pure {
+                anyhow::bail!(
+                    "Package '{}' is not a pure package tracked in the manifest; --to-lock only \
+                     resyncs pure packages pinned in the lock file",
+                    pkg
+                );
+            }
 
-        if python_upgraded {
-            println!();
-            println!("  {} Python was upgraded. You may want to recreate the venv:", "ℹ".cyan());
-            println!("    rm -rf .macdev/venv");
-            println!("    macdev install");
+            println!("{} {} to the lock file", "Resyncing".cyan(), pkg);
+            let local = local_manifest.as_ref().unwrap();
+            upgraded_count += resync_to_lock(local, Some(pkg_base))?;
         }
+    }
 
+    // Rebuild profile if any pure packages changed
+    if upgraded_count > 0
+        && let Some(local) = &local_manifest
+        && !local.packages.is_empty() {
+        println!("Rebuilding profile...");
+        rebuild_profile(local, false, false)?;
+    }
+
+    if python_upgraded {
         println!();
-        println!("{} Upgraded {} package(s)", "✓".green(), upgraded_count);
+        println!("  {} Python was upgraded. You may want to recreate the venv:", "ℹ".cyan());
+        println!("    rm -rf .macdev/venv");
+        println!("    macdev install");
     }
 
-    // Generate lock file
-    let _ = crate::manifest::generate_lock(); // Ignore errors
+    println!();
+    if matches!(policy, UpgradePolicy::None | UpgradePolicy::PackageToLock(_)) {
+        println!("{} Resynced {} package(s) to the lock", "✓".green(), upgraded_count);
+    } else {
+        println!("{} Upgraded {} package(s)", "✓".green(), upgraded_count);
+
+        // Re-resolve dependencies and refresh the lock (including metadata.generated).
+        // Skipped under `None`/`PackageToLock`, which are explicitly meant to leave
+        // the lock alone.
+        let _ = crate::manifest::generate_lock(); // Ignore errors
+    }
 
     Ok(())
 }
 
 /// Install all packages from manifest
-pub fn install() -> Result<()> {
+pub fn install(dry_run: bool, locked: bool, frozen: bool) -> Result<()> {
     use colored::*;
     use crate::manifest::Lock;
 
     let local_manifest = Manifest::load()?;
     let mut global_manifest = Manifest::load_global()?;
 
+    if locked && !Lock::exists() {
+        anyhow::bail!(
+            "--locked requires an existing lock file, but none was found. Run 'macdev install' \
+             without --locked to generate one."
+        );
+    }
+
     // Check if lock file exists - if so, use exact versions from lock
     let lock = if Lock::exists() {
+        let lock = Lock::load()?;
+        if locked && !lock.is_consistent_with(&local_manifest) {
+            anyhow::bail!(
+                "Manifest and lock file are out of sync. Run 'macdev install' without --locked \
+                 to regenerate the lock."
+            );
+        }
         println!("{}", "Installing from lock file...".cyan().bold());
-        Some(Lock::load()?)
+        Some(lock)
     } else {
         println!("{}", "Installing packages from manifest...".cyan().bold());
         None
     };
 
+    let mut tx = Transaction::new();
+
     // Install pure packages from local manifest (no link)
     for (name, version) in &local_manifest.packages {
         let spec = if let Some(lock) = &lock {
             // Use exact version from lock file if available
             if let Some(locked_pkg) = lock.packages.get(name) {
-                println!("  {} {} (locked: {})", "→".blue(), name, locked_pkg.version);
+                if !dry_run {
+                    println!("  {} {} (locked: {})", "→".blue(), name, locked_pkg.version);
+                }
                 locked_pkg.formula.clone()
             } else {
                 // Fallback to manifest spec if not in lock
@@ -559,17 +1155,69 @@ pub fn install() -> Result<()> {
             }
         };
 
+        if dry_run {
+            let already_installed = homebrew::is_package_installed(&spec).unwrap_or(false);
+            let status = if already_installed { "already installed" } else { "would install" };
+            println!("  {} {} ({})", "-".yellow(), spec, status);
+            continue;
+        }
+
         if lock.is_none() {
             println!("  {} {}", "→".blue(), spec);
         }
 
+        let already_installed = homebrew::is_package_installed(&spec).unwrap_or(false);
+        if frozen && !already_installed {
+            anyhow::bail!(
+                "{} is not installed and --frozen forbids fetching it from Homebrew. Run without \
+                 --frozen to install it.",
+                spec
+            );
+        }
         let brew_path = homebrew::ensure_package(&spec, false)?; // link=false
-        create_symlinks(&spec, &brew_path)?;
+        if !already_installed {
+            tx.record_install(spec.clone());
+        }
+
+        let variant = crate::brew_variant::BrewVariant::select(local_manifest.arch.as_deref());
+        homebrew::warn_if_wrong_variant(&brew_path, &variant);
+
+        // Verify what Homebrew actually placed on disk still matches what
+        // was pinned in the lock, so a tap silently republishing a
+        // different build under the same version doesn't go unnoticed.
+        // This runs before symlinks are created so a mismatch leaves the
+        // profile untouched instead of pointing at contents we just
+        // rejected.
+        if let Some(lock) = &lock
+            && let Some(locked_pkg) = lock.packages.get(name)
+            && let Some(expected_sha256) = &locked_pkg.sha256
+        {
+            let actual_sha256 = homebrew::hash_package_files(&brew_path)?;
+            if &actual_sha256 != expected_sha256 {
+                anyhow::bail!(
+                    "Integrity check failed for {}: installed contents do not match the lock \
+                     file (expected {}, got {}). The tap may have republished a different \
+                     build under the same version.",
+                    name,
+                    expected_sha256,
+                    actual_sha256
+                );
+            }
+        }
+
+        create_symlinks(&spec, &brew_path, false, false, &mut Vec::new())?;
 
         // Track in global manifest (it's now installed in Homebrew)
         global_manifest.add_package(name.clone(), version.clone());
     }
 
+    if dry_run {
+        return Ok(());
+    }
+
+    // Everything installed successfully: commit so Drop won't roll back
+    tx.success();
+
     // Save global manifest with newly installed pure packages
     if !local_manifest.packages.is_empty() {
         global_manifest.save_global()?;
@@ -585,32 +1233,44 @@ pub fn install() -> Result<()> {
     Ok(())
 }
 
-/// Create symlinks for a package
-fn create_symlinks(package: &str, brew_path: &Path) -> Result<()> {
+/// Create symlinks for a package. See [`link_directory`] for `dry_run`/`force` semantics.
+fn create_symlinks(
+    package: &str,
+    brew_path: &Path,
+    dry_run: bool,
+    force: bool,
+    failures: &mut Vec<String>,
+) -> Result<()> {
     let profile_dir = PathBuf::from(PROFILE_DIR);
-    fs::create_dir_all(&profile_dir)?;
+    if !dry_run {
+        fs::create_dir_all(&profile_dir)?;
+    }
 
     // Link bin directory
     let brew_bin = brew_path.join("bin");
     if brew_bin.exists() {
-        link_directory(&brew_bin, &profile_dir.join("bin"))?;
+        link_directory(&brew_bin, &profile_dir.join("bin"), dry_run, force, failures)?;
     }
 
     // ALSO link libexec/bin if it exists (this is where unversioned symlinks live)
     let libexec_bin = brew_path.join("libexec/bin");
     if libexec_bin.exists() {
-        link_directory(&libexec_bin, &profile_dir.join("bin"))?;
+        link_directory(&libexec_bin, &profile_dir.join("bin"), dry_run, force, failures)?;
     }
 
     // Link lib directory
     let brew_lib = brew_path.join("lib");
     if brew_lib.exists() {
-        link_directory(&brew_lib, &profile_dir.join("lib"))?;
+        link_directory(&brew_lib, &profile_dir.join("lib"), dry_run, force, failures)?;
     }
 
     // Special handling for Python: create virtual environment
     if package.starts_with("python") {
-        setup_python_venv(package)?;
+        if dry_run {
+            println!("    {} would set up Python virtual environment", "-".yellow());
+        } else {
+            setup_python_venv(package)?;
+        }
     }
 
     Ok(())
@@ -677,8 +1337,26 @@ fn setup_python_venv(_package: &str) -> Result<()> {
     Ok(())
 }
 
-/// Link all files from source directory to target directory
-fn link_directory(source: &Path, target: &Path) -> Result<()> {
+/// Link all files from source directory to target directory.
+///
+/// In `dry_run` mode this only prints what would be linked. In `force` mode
+/// a symlink failure is recorded in `failures` instead of aborting the rest
+/// of the directory.
+fn link_directory(
+    source: &Path,
+    target: &Path,
+    dry_run: bool,
+    force: bool,
+    failures: &mut Vec<String>,
+) -> Result<()> {
+    if dry_run {
+        for entry in fs::read_dir(source)? {
+            let entry = entry?;
+            println!("    {} would link {}", "-".yellow(), target.join(entry.file_name()).display());
+        }
+        return Ok(());
+    }
+
     fs::create_dir_all(target)?;
 
     for entry in fs::read_dir(source)? {
@@ -691,20 +1369,39 @@ fn link_directory(source: &Path, target: &Path) -> Result<()> {
             let _ = fs::remove_file(&target_path);
         }
 
-        unix_fs::symlink(entry.path(), target_path)?;
+        if let Err(e) = unix_fs::symlink(entry.path(), &target_path) {
+            if force {
+                failures.push(format!("failed to link {}: {}", target_path.display(), e));
+            } else {
+                return Err(e).context(format!("Failed to create symlink at {}", target_path.display()));
+            }
+        }
     }
 
     Ok(())
 }
 
-/// Rebuild the profile directory from scratch
-fn rebuild_profile(manifest: &Manifest) -> Result<()> {
+/// Rebuild the profile directory from scratch.
+///
+/// In `dry_run` mode, prints every mutation (directory removal, symlink
+/// creation) without performing any of them. In `force` mode, a failure
+/// linking one package is recorded instead of aborting the rest; the
+/// caller is responsible for reporting the returned failures to the user.
+fn rebuild_profile(manifest: &Manifest, dry_run: bool, force: bool) -> Result<Vec<String>> {
+    let mut failures = Vec::new();
     let profile_dir = PathBuf::from(PROFILE_DIR);
 
     // Delete entire profile directory
     if profile_dir.exists() {
-        fs::remove_dir_all(&profile_dir)
-            .context("Failed to remove profile directory")?;
+        if dry_run {
+            println!("  {} would remove {}", "-".yellow(), profile_dir.display());
+        } else if let Err(e) = fs::remove_dir_all(&profile_dir) {
+            if force {
+                failures.push(format!("failed to remove {}: {}", profile_dir.display(), e));
+            } else {
+                return Err(e).context("Failed to remove profile directory");
+            }
+        }
     }
 
     // Recreate symlinks for all remaining pure packages
@@ -718,16 +1415,32 @@ fn rebuild_profile(manifest: &Manifest) -> Result<()> {
                 format!("{}@{}", name, version)
             };
 
-            let brew_path = homebrew::package_prefix(&spec)?;
-            create_symlinks(&spec, &brew_path)?;
+            let brew_path = match homebrew::package_prefix(&spec) {
+                Ok(path) => path,
+                Err(e) if force => {
+                    failures.push(format!("failed to resolve prefix for {}: {}", spec, e));
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+
+            let variant = crate::brew_variant::BrewVariant::select(manifest.arch.as_deref());
+            homebrew::warn_if_wrong_variant(&brew_path, &variant);
+
+            create_symlinks(&spec, &brew_path, dry_run, force, &mut failures)?;
         }
     }
 
-    Ok(())
+    Ok(failures)
 }
 
-/// Add a Homebrew tap
-pub fn tap(tap_name: &str) -> Result<()> {
+/// Add one or more Homebrew taps, optionally from a custom git URL and/or
+/// with a full (non-shallow) clone. Each tap is processed independently and
+/// the global manifest is only saved once, at the end; a tap that's already
+/// tracked is skipped rather than treated as an error. Returns an error only
+/// if at least one tap genuinely failed to add, so partial success is
+/// visible in the per-tap output above.
+pub fn tap(tap_names: &[String], url: Option<String>, full: bool) -> Result<()> {
     use colored::*;
 
     // Check Homebrew is installed
@@ -735,56 +1448,195 @@ pub fn tap(tap_name: &str) -> Result<()> {
         anyhow::bail!("Homebrew is not installed. Install it from https://brew.sh");
     }
 
+    if url.is_some() && tap_names.len() > 1 {
+        anyhow::bail!("--url can only be used when tapping a single tap");
+    }
+
     let mut global_manifest = Manifest::load_global()?;
+    let mut failures = Vec::new();
+    let mut added = 0;
+
+    for tap_name in tap_names {
+        // Check if already tapped and tracked
+        if global_manifest.taps.contains_key(tap_name) {
+            println!("{} Tap '{}' is already tracked", "⚠".yellow(), tap_name);
+            continue;
+        }
 
-    // Check if already tapped and tracked
-    if global_manifest.taps.contains_key(tap_name) {
-        println!("{} Tap '{}' is already tracked", "⚠".yellow(), tap_name);
-        return Ok(());
-    }
+        println!("{} {}", "Adding tap".green(), tap_name);
+
+        // Add the tap if not already tapped
+        if !homebrew::is_tap_tapped(tap_name).unwrap_or(false) {
+            if let Err(e) = homebrew::tap(tap_name, url.as_deref(), full) {
+                failures.push(format!("failed to tap {}: {}", tap_name, e));
+                continue;
+            }
+        } else {
+            println!("  Tap already exists in Homebrew");
+        }
 
-    println!("{} {}", "Adding tap".green(), tap_name);
+        // Track in global manifest
+        global_manifest.add_tap(tap_name.clone(), url.clone(), full);
+        added += 1;
+    }
 
-    // Add the tap if not already tapped
-    if !homebrew::is_tap_tapped(tap_name)? {
-        homebrew::tap(tap_name)?;
-    } else {
-        println!("  Tap already exists in Homebrew");
+    if added > 0 {
+        global_manifest.save_global()?;
+        let path = Manifest::global_manifest_display_path()?;
+        println!("{} {} tap(s) added (saved to {})", "✓".green(), added, path);
     }
 
-    // Track in global manifest
-    global_manifest.add_tap(tap_name.to_string());
-    global_manifest.save_global()?;
+    report_failures(&failures);
 
-    let path = Manifest::global_manifest_display_path()?;
-    println!("{} Tap added (saved to {})", "✓".green(), path);
+    if !failures.is_empty() {
+        anyhow::bail!("{} of {} tap(s) failed to add", failures.len(), tap_names.len());
+    }
 
     Ok(())
 }
 
-/// Remove a Homebrew tap
-pub fn untap(tap_name: &str) -> Result<()> {
+/// Remove one or more Homebrew taps, processing each independently and
+/// saving the global manifest only once at the end. In `force` mode a
+/// failure untapping one tap doesn't stop the rest from being processed;
+/// either way, an error is returned if at least one tap genuinely failed,
+/// so partial success is visible in the per-tap output above.
+pub fn untap(tap_names: &[String], dry_run: bool, force: bool) -> Result<()> {
     use colored::*;
 
     let mut global_manifest = Manifest::load_global()?;
 
-    // Check if tap exists in manifest
-    if !global_manifest.taps.contains_key(tap_name) {
-        anyhow::bail!("Tap '{}' is not tracked", tap_name);
+    // Check all taps exist in the manifest before touching anything
+    let unknown: Vec<&String> = tap_names
+        .iter()
+        .filter(|t| !global_manifest.taps.contains_key(t.as_str()))
+        .collect();
+    if !unknown.is_empty() {
+        let names: Vec<&str> = unknown.iter().map(|s| s.as_str()).collect();
+        anyhow::bail!("Tap(s) not tracked: {}", names.join(", "));
     }
 
-    println!("{} {}", "Removing tap".yellow(), tap_name);
+    if dry_run {
+        for tap_name in tap_names {
+            println!("  {} would run `brew untap {}` and remove it from the manifest", "-".yellow(), tap_name);
+        }
+        return Ok(());
+    }
+
+    let mut failures = Vec::new();
+    let mut removed = 0;
 
-    // Remove from Homebrew
-    if homebrew::is_tap_tapped(tap_name)? {
-        homebrew::untap(tap_name)?;
+    for tap_name in tap_names {
+        println!("{} {}", "Removing tap".yellow(), tap_name);
+
+        if homebrew::is_tap_tapped(tap_name).unwrap_or(false)
+            && let Err(e) = homebrew::untap(tap_name)
+        {
+            failures.push(format!("failed to untap {}: {}", tap_name, e));
+            if !force {
+                break;
+            }
+            continue;
+        }
+
+        global_manifest.remove_tap(tap_name);
+        removed += 1;
     }
 
-    // Remove from global manifest
-    global_manifest.remove_tap(tap_name);
-    global_manifest.save_global()?;
+    if removed > 0 {
+        global_manifest.save_global()?;
+        println!("{} {} tap(s) removed", "✓".green(), removed);
+    }
+
+    report_failures(&failures);
+
+    if !failures.is_empty() {
+        anyhow::bail!("{} of {} tap(s) failed to untap", failures.len(), tap_names.len());
+    }
+
+    Ok(())
+}
+
+/// Status of a single macdev-tracked tap, mirroring Homebrew's own `tap-info`
+#[derive(Debug, Serialize)]
+pub struct TapStatus {
+    pub name: String,
+    pub tapped: bool,
+    pub path: Option<String>,
+    pub remote: Option<String>,
+    pub formula_count: usize,
+    pub cask_count: usize,
+}
+
+/// Report formula/cask counts and install status for tracked taps. With
+/// `names` empty, reports on every tap in the global manifest.
+pub fn tap_info(names: &[String], json: bool) -> Result<()> {
+    use colored::*;
+
+    let global_manifest = Manifest::load_global()?;
 
-    println!("{} Tap removed", "✓".green());
+    let tracked: Vec<String> = if names.is_empty() {
+        global_manifest.taps.keys().cloned().collect()
+    } else {
+        for name in names {
+            if !global_manifest.taps.contains_key(name) {
+                anyhow::bail!("Tap '{}' is not tracked", name);
+            }
+        }
+        names.to_vec()
+    };
+
+    let mut statuses: Vec<TapStatus> = tracked
+        .iter()
+        .map(|name| {
+            let info = homebrew::tap_info(name).unwrap_or(homebrew::TapInfo {
+                tapped: false,
+                path: None,
+                remote: None,
+                formula_count: 0,
+                cask_count: 0,
+            });
+            TapStatus {
+                name: name.clone(),
+                tapped: info.tapped,
+                path: info.path.map(|p| p.display().to_string()),
+                remote: info.remote,
+                formula_count: info.formula_count,
+                cask_count: info.cask_count,
+            }
+        })
+        .collect();
+    statuses.sort_by(|a, b| a.name.cmp(&b.name));
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&statuses)?);
+        return Ok(());
+    }
+
+    if statuses.is_empty() {
+        println!("{}", "No taps tracked".yellow());
+        return Ok(());
+    }
+
+    for (i, status) in statuses.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+
+        if status.tapped {
+            println!("{} {}", "✓".green(), status.name.bold());
+        } else {
+            println!("{} {} (not tapped)", "✗".red(), status.name.bold());
+        }
+
+        if let Some(path) = &status.path {
+            println!("    Path:    {}", path.bright_black());
+        }
+        if let Some(remote) = &status.remote {
+            println!("    Remote:  {}", remote.bright_black());
+        }
+        println!("    Formulae: {}", status.formula_count);
+        println!("    Casks:    {}", status.cask_count);
+    }
 
     Ok(())
 }
@@ -1,6 +1,45 @@
 use anyhow::{Context, Result};
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::{Mutex, OnceLock};
+
+/// Memoized set of currently-tapped taps, populated from `brew tap` on
+/// first use. `tap`/`untap` keep this in sync as they change tap state, so
+/// repeated `is_tap_tapped` checks during a rebuild don't each spawn a
+/// `brew` subprocess.
+fn tap_cache() -> &'static Mutex<HashSet<String>> {
+    static CACHE: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(installed_taps().unwrap_or_default()))
+}
+
+/// Memoized package-prefix lookups, populated lazily per package as
+/// `package_prefix` is called. Invalidated for a package when it's
+/// uninstalled, since its prefix no longer resolves.
+fn prefix_cache() -> &'static Mutex<HashMap<String, PathBuf>> {
+    static CACHE: OnceLock<Mutex<HashMap<String, PathBuf>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// List every tap Homebrew currently has installed
+pub fn installed_taps() -> Result<HashSet<String>> {
+    let output = Command::new("brew")
+        .args(["tap"])
+        .output()
+        .context("Failed to run 'brew tap'")?;
+
+    if !output.status.success() {
+        return Ok(HashSet::new());
+    }
+
+    let taps_output = String::from_utf8(output.stdout)?;
+    Ok(taps_output
+        .lines()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect())
+}
 
 /// Check if Homebrew is installed
 pub fn is_installed() -> bool {
@@ -61,8 +100,143 @@ pub fn unlink_package(package: &str) -> Result<()> {
     Ok(())
 }
 
+/// Relink a package (restore to global availability)
+pub fn relink_package(package: &str) -> Result<()> {
+    let output = Command::new("brew")
+        .args(["link", "--overwrite", package])
+        .output()
+        .context("Failed to run 'brew link'")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("{}", stderr.trim());
+    }
+
+    Ok(())
+}
+
+/// Resolved version and formula name for an installed package
+pub struct PackageInfo {
+    pub version: String,
+    pub formula: String,
+}
+
+/// Get the resolved version and formula name for an installed package
+pub fn package_info(package: &str) -> Result<PackageInfo> {
+    let output = Command::new("brew")
+        .args(["list", "--versions", package])
+        .output()
+        .context(format!("Failed to get info for {}", package))?;
+
+    if !output.status.success() {
+        anyhow::bail!("Package {} is not installed", package);
+    }
+
+    let stdout = String::from_utf8(output.stdout)?;
+    let line = stdout
+        .lines()
+        .next()
+        .context(format!("No version info for {}", package))?;
+
+    let mut parts = line.split_whitespace();
+    let formula = parts
+        .next()
+        .context(format!("No version info for {}", package))?
+        .to_string();
+    let version = parts
+        .next()
+        .context(format!("No version info for {}", package))?
+        .to_string();
+
+    Ok(PackageInfo { version, formula })
+}
+
+/// Get the currently installed version of a package, if installed
+pub fn installed_version(package: &str) -> Result<Option<String>> {
+    if !is_package_installed(package)? {
+        return Ok(None);
+    }
+
+    Ok(Some(package_info(package)?.version))
+}
+
+/// Check whether an installed package has a newer version available upstream
+pub fn is_outdated(package: &str) -> Result<bool> {
+    let output = Command::new("brew")
+        .args(["outdated", "--formula", package])
+        .output()
+        .context("Failed to run 'brew outdated'")?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(!stdout.trim().is_empty())
+}
+
+/// Parse `brew outdated`'s `name (installed) < available` line for a single
+/// package, returning the `(installed, available)` versions if it's
+/// outdated, or `None` if it's already current.
+pub fn outdated_versions(package: &str) -> Result<Option<(String, String)>> {
+    let output = Command::new("brew")
+        .args(["outdated", "--formula", package])
+        .output()
+        .context("Failed to run 'brew outdated'")?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let line = match stdout.lines().next() {
+        Some(line) if !line.trim().is_empty() => line,
+        _ => return Ok(None),
+    };
+
+    let (name_part, available) = line
+        .split_once('<')
+        .context("Unexpected 'brew outdated' output format")?;
+    let installed = name_part
+        .split('(')
+        .nth(1)
+        .and_then(|s| s.split(')').next())
+        .context("Unexpected 'brew outdated' output format")?;
+
+    Ok(Some((installed.trim().to_string(), available.trim().to_string())))
+}
+
+/// Warn if a resolved package prefix doesn't belong to the selected
+/// `BrewVariant`, which can happen on machines with both an Intel and an
+/// Apple Silicon Homebrew install on `PATH`.
+pub fn warn_if_wrong_variant(prefix: &Path, variant: &crate::brew_variant::BrewVariant) {
+    use colored::*;
+
+    if !prefix.starts_with(variant.prefix()) {
+        println!(
+            "  {} {} is not under the selected Homebrew variant ({}); \
+             the wrong architecture's binaries may get linked. Pin `arch` in the manifest to fix this.",
+            "⚠".yellow(),
+            prefix.display(),
+            variant
+        );
+    }
+}
+
+/// Get Homebrew's own installation prefix (not a specific package's)
+pub fn prefix() -> Result<PathBuf> {
+    let output = Command::new("brew")
+        .arg("--prefix")
+        .output()
+        .context("Failed to get Homebrew prefix")?;
+
+    if !output.status.success() {
+        anyhow::bail!("Failed to resolve Homebrew prefix");
+    }
+
+    let prefix = String::from_utf8(output.stdout)?.trim().to_string();
+
+    Ok(PathBuf::from(prefix))
+}
+
 /// Get the installation path for a package
 pub fn package_prefix(package: &str) -> Result<PathBuf> {
+    if let Some(prefix) = prefix_cache().lock().unwrap().get(package) {
+        return Ok(prefix.clone());
+    }
+
     let output = Command::new("brew")
         .args(["--prefix", package])
         .output()
@@ -72,9 +246,10 @@ pub fn package_prefix(package: &str) -> Result<PathBuf> {
         anyhow::bail!("Package {} is not installed", package);
     }
 
-    let prefix = String::from_utf8(output.stdout)?.trim().to_string();
+    let prefix = PathBuf::from(String::from_utf8(output.stdout)?.trim().to_string());
+    prefix_cache().lock().unwrap().insert(package.to_string(), prefix.clone());
 
-    Ok(PathBuf::from(prefix))
+    Ok(prefix)
 }
 
 /// Uninstall a package
@@ -89,6 +264,8 @@ pub fn uninstall_package(package: &str) -> Result<()> {
         anyhow::bail!("{}", stderr.trim());
     }
 
+    prefix_cache().lock().unwrap().remove(package);
+
     Ok(())
 }
 
@@ -119,6 +296,94 @@ pub fn ensure_package(package: &str, link: bool) -> Result<PathBuf> {
     package_prefix(package)
 }
 
+/// Check if a cask is installed
+pub fn is_cask_installed(cask: &str) -> Result<bool> {
+    let output = Command::new("brew").args(["list", "--cask", cask]).output()?;
+
+    Ok(output.status.success())
+}
+
+/// Install a cask (GUI application). Casks can't be symlinked into the
+/// project profile, so they always behave as system-wide installs.
+pub fn install_cask(cask: &str) -> Result<()> {
+    use colored::*;
+
+    println!("  Installing {} via Homebrew Cask...", cask.cyan());
+
+    let status = Command::new("brew")
+        .args(["install", "--cask", cask])
+        .status()
+        .context("Failed to run 'brew install --cask'")?;
+
+    if !status.success() {
+        anyhow::bail!("Failed to install cask {}", cask);
+    }
+
+    Ok(())
+}
+
+/// Uninstall a cask
+pub fn uninstall_cask(cask: &str) -> Result<()> {
+    let output = Command::new("brew")
+        .args(["uninstall", "--cask", cask])
+        .output()
+        .context("Failed to run 'brew uninstall --cask'")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("{}", stderr.trim());
+    }
+
+    Ok(())
+}
+
+/// Ensure a cask is installed
+pub fn ensure_cask(cask: &str) -> Result<()> {
+    if !is_cask_installed(cask)? {
+        install_cask(cask)?;
+    }
+
+    Ok(())
+}
+
+/// List every cask currently installed via Homebrew
+pub fn installed_casks() -> Result<Vec<String>> {
+    let output = Command::new("brew")
+        .args(["list", "--cask"])
+        .output()
+        .context("Failed to run 'brew list --cask'")?;
+
+    if !output.status.success() {
+        return Ok(Vec::new());
+    }
+
+    let stdout = String::from_utf8(output.stdout)?;
+    Ok(stdout
+        .lines()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect())
+}
+
+/// List every formula currently installed via Homebrew
+pub fn installed_formulae() -> Result<Vec<String>> {
+    let output = Command::new("brew")
+        .args(["list", "--formula"])
+        .output()
+        .context("Failed to run 'brew list'")?;
+
+    if !output.status.success() {
+        return Ok(Vec::new());
+    }
+
+    let stdout = String::from_utf8(output.stdout)?;
+    Ok(stdout
+        .lines()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect())
+}
+
 /// Get list of dependencies for a package
 pub fn package_deps(package: &str) -> Result<Vec<String>> {
     let output = Command::new("brew")
@@ -140,29 +405,112 @@ pub fn package_deps(package: &str) -> Result<Vec<String>> {
     Ok(deps)
 }
 
-/// Check if a tap is already tapped
-pub fn is_tap_tapped(tap: &str) -> Result<bool> {
+/// Recursively hash every regular file under a package's installed prefix,
+/// in sorted path order, into a single SHA-256 digest. The same function is
+/// used to compute the value pinned in the lock file and, later, to
+/// recompute it at install time — a mismatch means what Homebrew placed on
+/// disk for this formula/version has changed since the lock was generated
+/// (e.g. a tap silently republishing a different build under the same
+/// version), not just that the version string changed.
+pub fn hash_package_files(dir: &Path) -> Result<String> {
+    use sha2::{Digest, Sha256};
+    use std::io::Read;
+
+    let mut paths = Vec::new();
+    collect_files(dir, &mut paths)?;
+    paths.sort();
+
+    let mut hasher = Sha256::new();
+    let mut buf = [0u8; 8192];
+
+    for path in paths {
+        let mut file = fs::File::open(&path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        loop {
+            let n = file.read(&mut buf)?;
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buf[..n]);
+        }
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Depth-first collection of every regular file under `dir`. Symlinks are
+/// skipped since most of a Homebrew Cellar entry's own `bin`/`lib` layout is
+/// just symlinks back into itself.
+fn collect_files(dir: &Path, out: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+
+        if file_type.is_symlink() {
+            continue;
+        } else if file_type.is_dir() {
+            collect_files(&path, out)?;
+        } else if file_type.is_file() {
+            out.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// List the version suffixes Homebrew offers for a package (e.g. for
+/// "python" this enumerates "3.11", "3.12", ... from sibling formulae like
+/// `python@3.11`), plus the bare formula's own version if it is installed.
+pub fn available_versions(name: &str) -> Result<Vec<String>> {
+    let mut versions = Vec::new();
+
     let output = Command::new("brew")
-        .args(["tap"])
+        .args(["search", &format!("/^{}@/", name)])
         .output()
-        .context("Failed to run 'brew tap'")?;
+        .context("Failed to run 'brew search'")?;
+
+    if output.status.success() {
+        let stdout = String::from_utf8(output.stdout)?;
+        for line in stdout.lines() {
+            let line = line.trim();
+            if let Some(version) = line.strip_prefix(&format!("{}@", name)) {
+                versions.push(version.to_string());
+            }
+        }
+    }
 
-    if !output.status.success() {
-        return Ok(false);
+    if let Ok(info) = package_info(name) {
+        versions.push(info.version);
     }
 
-    let taps_output = String::from_utf8(output.stdout)?;
-    Ok(taps_output.lines().any(|line| line.trim() == tap))
+    Ok(versions)
 }
 
-/// Add a tap
-pub fn tap(tap_name: &str) -> Result<()> {
+/// Check if a tap is already tapped
+pub fn is_tap_tapped(tap: &str) -> Result<bool> {
+    Ok(tap_cache().lock().unwrap().contains(tap))
+}
+
+/// Add a tap, optionally from a custom git URL (ssh/https/file, as Homebrew
+/// itself supports) instead of the default GitHub location. Homebrew clones
+/// taps shallowly by default; pass `full` to force a full clone, needed when
+/// a tap's git history is required for `brew update` or bottle provenance.
+pub fn tap(tap_name: &str, url: Option<&str>, full: bool) -> Result<()> {
     use colored::*;
 
     println!("  Tapping {} via Homebrew...", tap_name.cyan());
 
+    let mut args = vec!["tap", tap_name];
+    if let Some(url) = url {
+        args.push(url);
+    }
+    if full {
+        args.push("--full");
+    }
+
     let status = Command::new("brew")
-        .args(["tap", tap_name])
+        .args(&args)
         .status()
         .context("Failed to run 'brew tap'")?;
 
@@ -170,9 +518,91 @@ pub fn tap(tap_name: &str) -> Result<()> {
         anyhow::bail!("Failed to tap {}", tap_name);
     }
 
+    tap_cache().lock().unwrap().insert(tap_name.to_string());
+
     Ok(())
 }
 
+/// On-disk repository location, remote origin URL, and formula/cask counts
+/// for a tap, as scanned from its clone under Homebrew's Taps directory
+pub struct TapInfo {
+    pub tapped: bool,
+    pub path: Option<PathBuf>,
+    pub remote: Option<String>,
+    pub formula_count: usize,
+    pub cask_count: usize,
+}
+
+/// Resolve a tap's on-disk directory under `$(brew --prefix)/Library/Taps`
+/// (e.g. "user/cask" -> ".../Library/Taps/user/homebrew-cask")
+fn tap_path(tap_name: &str) -> Result<PathBuf> {
+    let (user, repo) = tap_name
+        .split_once('/')
+        .context("Tap name must be in 'user/repo' form")?;
+    let repo_dir = if repo.starts_with("homebrew-") {
+        repo.to_string()
+    } else {
+        format!("homebrew-{}", repo)
+    };
+
+    Ok(prefix()?.join("Library/Taps").join(user).join(repo_dir))
+}
+
+/// Count `.rb` files directly inside a directory (not recursive)
+fn count_rb_files(dir: &Path) -> usize {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| e.path().extension().is_some_and(|ext| ext == "rb"))
+                .count()
+        })
+        .unwrap_or(0)
+}
+
+/// Report whether a tap is actually present in Homebrew, plus its on-disk
+/// path, remote origin URL, and formula/cask counts scanned from the clone
+pub fn tap_info(tap_name: &str) -> Result<TapInfo> {
+    let tapped = is_tap_tapped(tap_name).unwrap_or(false);
+    if !tapped {
+        return Ok(TapInfo {
+            tapped: false,
+            path: None,
+            remote: None,
+            formula_count: 0,
+            cask_count: 0,
+        });
+    }
+
+    let path = tap_path(tap_name)?;
+
+    let remote = Command::new("git")
+        .args(["-C", &path.to_string_lossy(), "remote", "get-url", "origin"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .and_then(|o| String::from_utf8(o.stdout).ok())
+        .map(|s| s.trim().to_string());
+
+    // Newer taps keep formulae under Formula/; older ones keep them at the
+    // repository root alongside everything else
+    let formula_dir = path.join("Formula");
+    let formula_count = if formula_dir.is_dir() {
+        count_rb_files(&formula_dir)
+    } else {
+        count_rb_files(&path)
+    };
+    let cask_count = count_rb_files(&path.join("Casks"));
+
+    Ok(TapInfo {
+        tapped,
+        path: Some(path),
+        remote,
+        formula_count,
+        cask_count,
+    })
+}
+
 /// Remove a tap
 pub fn untap(tap_name: &str) -> Result<()> {
     use colored::*;
@@ -188,5 +618,7 @@ pub fn untap(tap_name: &str) -> Result<()> {
         anyhow::bail!("Failed to untap {}", tap_name);
     }
 
+    tap_cache().lock().unwrap().remove(tap_name);
+
     Ok(())
 }
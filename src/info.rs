@@ -0,0 +1,237 @@
+use anyhow::Result;
+use colored::*;
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::homebrew;
+use crate::manifest::{Lock, Manifest};
+
+/// Structured environment report, similar in spirit to `brew doctor`/`cargo --version -v`
+#[derive(Debug, Serialize)]
+pub struct InfoReport {
+    pub homebrew_installed: bool,
+    pub homebrew_version: Option<String>,
+    pub homebrew_prefix: Option<String>,
+    pub macdev_version: String,
+    pub shell: String,
+    pub manifest_exists: bool,
+    pub lock_exists: bool,
+    pub manifest_lock_in_sync: bool,
+    pub lock_generated: Option<String>,
+    pub manifest_package_count: usize,
+    pub impure_count: usize,
+    pub tap_count: usize,
+    pub drift: Vec<String>,
+    pub packages: Vec<PackageStatus>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PackageStatus {
+    pub name: String,
+    pub locked_version: Option<String>,
+    pub installed_version: Option<String>,
+    pub profile_version: Option<String>,
+    pub in_sync: bool,
+}
+
+/// Gather diagnostic info about the environment
+pub fn gather() -> Result<InfoReport> {
+    let homebrew_installed = homebrew::is_installed();
+    let homebrew_version = if homebrew_installed { brew_version() } else { None };
+    let homebrew_prefix = homebrew::prefix().ok().map(|p| p.display().to_string());
+
+    let local_manifest = Manifest::load().ok();
+    let global_manifest = Manifest::load_global()?;
+    let lock = Lock::load().ok();
+
+    let macdev_version = lock
+        .as_ref()
+        .map(|l| l.metadata.macdev_version.clone())
+        .unwrap_or_else(|| env!("CARGO_PKG_VERSION").to_string());
+    let lock_generated = lock.as_ref().map(|l| l.metadata.generated.clone());
+    let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/bash".to_string());
+
+    let manifest_exists = Manifest::exists();
+    let lock_exists = Lock::exists();
+    let manifest_lock_in_sync = match (&local_manifest, &lock) {
+        (Some(local), Some(lock)) => lock.is_consistent_with(local),
+        (None, None) => true,
+        _ => false,
+    };
+
+    let mut drift = Vec::new();
+    let mut packages = Vec::new();
+
+    if let Some(local) = &local_manifest {
+        for name in local.packages.keys() {
+            if !lock.as_ref().is_some_and(|l| l.packages.contains_key(name)) {
+                drift.push(format!("{} is in the manifest but not locked", name));
+            }
+        }
+    }
+
+    if let Some(lock) = &lock {
+        for name in lock.packages.keys() {
+            if !local_manifest.as_ref().is_some_and(|m| m.packages.contains_key(name)) {
+                drift.push(format!("{} is locked but not in the manifest", name));
+            }
+        }
+
+        for (name, locked) in &lock.packages {
+            // Compared against the live Homebrew/Cellar state, so a direct
+            // `brew upgrade` run outside macdev still shows up as drift
+            // even though it never touches the profile symlinks.
+            let installed = homebrew::installed_version(&locked.formula).ok().flatten();
+            let profile_version = profile_linked_version(&locked.formula);
+            let in_sync = installed.as_deref() == Some(locked.version.as_str());
+            packages.push(PackageStatus {
+                name: name.clone(),
+                locked_version: Some(locked.version.clone()),
+                installed_version: installed,
+                profile_version,
+                in_sync,
+            });
+        }
+    }
+
+    packages.sort_by(|a, b| a.name.cmp(&b.name));
+    drift.sort();
+
+    Ok(InfoReport {
+        homebrew_installed,
+        homebrew_version,
+        homebrew_prefix,
+        macdev_version,
+        shell,
+        manifest_exists,
+        lock_exists,
+        manifest_lock_in_sync,
+        lock_generated,
+        manifest_package_count: local_manifest.as_ref().map_or(0, |m| m.packages.len()),
+        impure_count: global_manifest.impure.len(),
+        tap_count: global_manifest.taps.len(),
+        drift,
+        packages,
+    })
+}
+
+/// Version of `formula` currently linked into `.macdev/profile`, determined
+/// by resolving the profile's bin symlinks back to their Cellar path
+/// rather than querying Homebrew directly. This reflects what the profile
+/// was last rebuilt against, which can lag behind the Cellar if packages
+/// were upgraded without a `macdev install`/`sync`.
+fn profile_linked_version(formula: &str) -> Option<String> {
+    let bin_dir = PathBuf::from(".macdev/profile/bin");
+    let entries = fs::read_dir(&bin_dir).ok()?;
+    let marker = format!("/Cellar/{}/", formula);
+
+    for entry in entries.flatten() {
+        if let Ok(target) = fs::read_link(entry.path()) {
+            let target = target.to_string_lossy();
+            if let Some(idx) = target.find(&marker) {
+                let rest = &target[idx + marker.len()..];
+                if let Some(version) = rest.split('/').next() {
+                    return Some(version.to_string());
+                }
+            }
+        }
+    }
+
+    None
+}
+
+fn brew_version() -> Option<String> {
+    use std::process::Command;
+
+    let output = Command::new("brew").arg("--version").output().ok()?;
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    stdout.lines().next().map(|s| s.to_string())
+}
+
+/// Print the environment report, either as colored text or as JSON
+pub fn info(json: bool) -> Result<()> {
+    let report = gather()?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    println!("{}", "macdev environment info".cyan().bold());
+    println!();
+
+    if report.homebrew_installed {
+        println!("  Homebrew: {}", "installed".green());
+        if let Some(version) = &report.homebrew_version {
+            println!("    {}", version.bright_black());
+        }
+        if let Some(prefix) = &report.homebrew_prefix {
+            println!("    prefix: {}", prefix.bright_black());
+        }
+    } else {
+        println!("  Homebrew: {}", "not installed".red());
+    }
+
+    println!("  macdev version: {}", report.macdev_version);
+    println!("  Shell:          {}", report.shell);
+
+    let manifest_status = if report.manifest_exists { "found".green() } else { "missing".red() };
+    println!("  Manifest: {}", manifest_status);
+    let lock_status = if report.lock_exists { "found".green() } else { "missing".bright_black() };
+    println!("  Lock:     {}", lock_status);
+    if report.manifest_exists || report.lock_exists {
+        let sync_status =
+            if report.manifest_lock_in_sync { "in sync".green() } else { "out of sync".yellow() };
+        println!("  Manifest/lock: {}", sync_status);
+    }
+
+    match &report.lock_generated {
+        Some(generated) => println!("  Lock generated: {}", generated),
+        None => println!("  Lock generated: {}", "no lock file".bright_black()),
+    }
+
+    println!();
+    println!("  Manifest packages: {}", report.manifest_package_count);
+    println!("  Impure packages:   {}", report.impure_count);
+    println!("  Taps:              {}", report.tap_count);
+
+    if !report.drift.is_empty() {
+        println!();
+        println!("{}", "Drift between manifest and lock:".yellow().bold());
+        for item in &report.drift {
+            println!("  {} {}", "⚠".yellow(), item);
+        }
+    }
+
+    if !report.packages.is_empty() {
+        println!();
+        println!("{}", "Package versions:".blue().bold());
+        for pkg in &report.packages {
+            let locked = pkg.locked_version.as_deref().unwrap_or("-");
+            if pkg.in_sync {
+                println!("  {} {} @ {}", "✓".green(), pkg.name, locked);
+            } else {
+                let installed = pkg.installed_version.as_deref().unwrap_or("missing");
+                println!(
+                    "  {} {} locked={} installed={}",
+                    "⚠".yellow(),
+                    pkg.name,
+                    locked,
+                    installed
+                );
+            }
+            if let Some(profile_version) = &pkg.profile_version
+                && Some(profile_version.as_str()) != pkg.installed_version.as_deref()
+            {
+                println!(
+                    "      {} profile is linked against {}",
+                    "·".bright_black(),
+                    profile_version
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
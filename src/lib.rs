@@ -0,0 +1,10 @@
+pub mod brew_variant;
+pub mod cli;
+pub mod doctor;
+pub mod environment;
+pub mod homebrew;
+pub mod info;
+pub mod manifest;
+pub mod shell;
+pub mod transaction;
+pub mod version;
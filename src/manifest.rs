@@ -27,7 +27,30 @@ pub struct Manifest {
     pub gc: HashMap<String, String>,
 
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
-    pub taps: HashMap<String, bool>,
+    pub taps: HashMap<String, TapEntry>,
+
+    /// Homebrew variant this project is pinned to ("arm", "intel", or an
+    /// explicit prefix path). `None` means auto-detect.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arch: Option<String>,
+}
+
+/// How a tap was added, so `untap`/re-tap and environment rebuilds can
+/// reproduce the exact same `brew tap` invocation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct TapEntry {
+    /// Custom git URL the tap was added from (ssh/https/file), if not the
+    /// default GitHub location
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    /// Whether the tap was cloned in full, instead of Homebrew's default shallow clone
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub full: bool,
+}
+
+fn is_false(b: &bool) -> bool {
+    !*b
 }
 
 impl Manifest {
@@ -100,6 +123,7 @@ impl Manifest {
             casks: HashMap::new(),
             gc: HashMap::new(),
             taps: HashMap::new(),
+            arch: self.arch.clone(),
         };
 
         let contents = toml::to_string_pretty(&local_only)
@@ -131,9 +155,9 @@ impl Manifest {
         self.casks.remove(name);
     }
 
-    /// Add a tap
-    pub fn add_tap(&mut self, name: String) {
-        self.taps.insert(name, true);
+    /// Add a tap, recording the git URL and clone mode it was added with
+    pub fn add_tap(&mut self, name: String, url: Option<String>, full: bool) {
+        self.taps.insert(name, TapEntry { url, full });
     }
 
     /// Remove a tap
@@ -141,6 +165,11 @@ impl Manifest {
         self.taps.remove(name);
     }
 
+    /// Pin the Homebrew variant this project targets
+    pub fn set_arch(&mut self, arch: String) {
+        self.arch = Some(arch);
+    }
+
     /// Remove a package
     pub fn remove_package(&mut self, name: &str) {
         self.packages.remove(name);
@@ -155,20 +184,23 @@ impl Manifest {
 }
 
 /// Initialize a new manifest
-pub fn init() -> Result<()> {
+pub fn init(arch: Option<String>) -> Result<()> {
     use colored::*;
-    
+
     if Manifest::exists() {
         println!("{}", "Manifest already exists".yellow());
         return Ok(());
     }
-    
-    let manifest = Manifest::default();
+
+    let mut manifest = Manifest::default();
+    if let Some(arch) = arch {
+        manifest.set_arch(arch);
+    }
     manifest.save()?;
-    
+
     println!("{}", "✓ Initialized macdev environment".green());
     println!("  Created {}", MANIFEST_FILE.bright_black());
-    
+
     Ok(())
 }
 
@@ -208,8 +240,11 @@ pub fn list() -> Result<()> {
 
     if !global_manifest.taps.is_empty() {
         println!("{}", format!("Taps (from {}):", global_path).magenta().bold());
-        for name in global_manifest.taps.keys() {
-            println!("  {}", name);
+        for (name, entry) in &global_manifest.taps {
+            match &entry.url {
+                Some(url) => println!("  {} ({}{})", name, url, if entry.full { ", full clone" } else { "" }),
+                None => println!("  {}", name),
+            }
         }
     }
 
@@ -274,6 +309,12 @@ pub struct LockMetadata {
 pub struct LockedPackage {
     pub version: String,
     pub formula: String,
+    /// SHA-256 of the package's installed file contents, if it could be
+    /// computed at lock time. Pins the exact bottle contents, not just the
+    /// version string, so a tap silently republishing a different build
+    /// under the same version is caught on install rather than trusted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
 }
 
 impl Default for Lock {
@@ -333,21 +374,149 @@ impl Lock {
     }
 
     /// Add a package to the lock
-    pub fn add_package(&mut self, name: String, version: String, formula: String) {
-        self.packages.insert(name, LockedPackage { version, formula });
+    pub fn add_package(&mut self, name: String, version: String, formula: String, sha256: Option<String>) {
+        self.packages.insert(name, LockedPackage { version, formula, sha256 });
     }
 
     /// Add a dependency to the lock
-    pub fn add_dependency(&mut self, package: String, dep: String, version: String, formula: String) {
+    pub fn add_dependency(
+        &mut self,
+        package: String,
+        dep: String,
+        version: String,
+        formula: String,
+        sha256: Option<String>,
+    ) {
         let key = format!("{}:{}", package, dep);
-        self.dependencies.insert(key, LockedPackage { version, formula });
+        self.dependencies.insert(key, LockedPackage { version, formula, sha256 });
+    }
+
+    /// Whether this lock still matches `manifest`: the same set of pure
+    /// packages, each locked version satisfying its manifest constraint.
+    /// `--locked`/`--frozen` installs refuse to proceed when this is false
+    /// instead of silently regenerating the lock out from under them.
+    pub fn is_consistent_with(&self, manifest: &Manifest) -> bool {
+        if self.packages.len() != manifest.packages.len() {
+            return false;
+        }
+
+        manifest.packages.iter().all(|(name, constraint)| {
+            self.packages
+                .get(name)
+                .is_some_and(|locked| crate::version::satisfies(constraint, &locked.version))
+        })
     }
+
+    /// Diff this lock (the previous state) against `other` (a freshly
+    /// computed lock), covering both top-level packages and the transitive
+    /// dependency versions tracked in `dependencies`.
+    pub fn diff(&self, other: &Lock) -> LockDiff {
+        let mut old_entries: HashMap<String, String> = HashMap::new();
+        for (name, pkg) in &self.packages {
+            old_entries.insert(name.clone(), pkg.version.clone());
+        }
+        for (key, dep) in &self.dependencies {
+            old_entries.insert(key.clone(), dep.version.clone());
+        }
+
+        let mut new_entries: HashMap<String, String> = HashMap::new();
+        for (name, pkg) in &other.packages {
+            new_entries.insert(name.clone(), pkg.version.clone());
+        }
+        for (key, dep) in &other.dependencies {
+            new_entries.insert(key.clone(), dep.version.clone());
+        }
+
+        let mut added = Vec::new();
+        let mut removed = Vec::new();
+        let mut changed = Vec::new();
+
+        for (name, version) in &new_entries {
+            match old_entries.get(name) {
+                None => added.push((name.clone(), version.clone())),
+                Some(old_version) if old_version != version => {
+                    changed.push((name.clone(), old_version.clone(), version.clone()))
+                }
+                _ => {}
+            }
+        }
+
+        for (name, version) in &old_entries {
+            if !new_entries.contains_key(name) {
+                removed.push((name.clone(), version.clone()));
+            }
+        }
+
+        added.sort();
+        removed.sort();
+        changed.sort();
+
+        LockDiff { added, removed, changed }
+    }
+}
+
+/// Added/removed/version-changed packages between two `Lock`s
+#[derive(Debug, Default)]
+pub struct LockDiff {
+    pub added: Vec<(String, String)>,
+    pub removed: Vec<(String, String)>,
+    pub changed: Vec<(String, String, String)>,
+}
+
+impl LockDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Print the diff as a `+`/`-`/`~` change summary, uv-install-output style
+    pub fn print(&self) {
+        use colored::*;
+
+        for (name, version) in &self.added {
+            println!("  {} {} {}", "+".green().bold(), name, version);
+        }
+        for (name, version) in &self.removed {
+            println!("  {} {} {}", "-".red().bold(), name, version);
+        }
+        for (name, old, new) in &self.changed {
+            println!("  {} {} {} -> {}", "~".yellow().bold(), name, old, new);
+        }
+    }
+}
+
+/// Resolved root package, along with the (not yet deduplicated) names of
+/// its direct dependencies, as produced by the first parallel resolution
+/// pass in `generate_lock`.
+struct RootResolution {
+    name: String,
+    info: crate::homebrew::PackageInfo,
+    sha256: Option<String>,
+    deps: Vec<String>,
+}
+
+/// Resolved dependency, as produced by the second parallel resolution pass
+/// in `generate_lock`, once shared dependencies have been deduplicated
+/// across all roots.
+struct DepResolution {
+    spec: String,
+    info: crate::homebrew::PackageInfo,
+    sha256: Option<String>,
+}
+
+fn resolve_for_lock(formula: &str) -> Option<(crate::homebrew::PackageInfo, Option<String>)> {
+    let info = crate::homebrew::package_info(formula).ok()?;
+    let sha256 = crate::homebrew::package_prefix(&info.formula)
+        .and_then(|prefix| crate::homebrew::hash_package_files(&prefix))
+        .ok();
+    Some((info, sha256))
 }
 
 /// Generate lock file from current local manifest (project-specific only)
 pub fn generate_lock() -> Result<()> {
     use crate::homebrew;
     use colored::*;
+    use rayon::prelude::*;
+    use std::collections::BTreeSet;
 
     // Only lock packages from LOCAL manifest (project-specific)
     // Do not lock global/impure packages (those are personal system tools)
@@ -358,39 +527,92 @@ pub fn generate_lock() -> Result<()> {
     }
 
     println!("  {} Generating lock file...", "→".blue());
+    let previous_lock = Lock::load().ok();
     let mut lock = Lock::new();
 
-    // Lock all pure packages from this project and their dependencies
-    for (name, version) in &local_manifest.packages {
-        // Reconstruct package spec (e.g., "python" + "3.12" -> "python@3.12")
-        let spec = if version == "*" {
-            name.clone()
-        } else {
-            format!("{}@{}", name, version)
-        };
-
-        // Get package info
-        let info = homebrew::package_info(&spec)?;
-        lock.add_package(name.clone(), info.version.clone(), info.formula);
-        println!("    Locked {} @ {}", name, info.version);
-
-        // Get and lock dependencies
-        let deps = homebrew::package_deps(&spec)?;
-        if !deps.is_empty() {
-            println!("      Locking {} dependencies...", deps.len());
+    let mut roots: Vec<(&String, &String)> = local_manifest.packages.iter().collect();
+    roots.sort_by(|a, b| a.0.cmp(b.0));
+
+    // Resolve every root package concurrently. A root that fails to resolve
+    // aborts lock generation.
+    let mut root_results: Vec<RootResolution> = roots
+        .par_iter()
+        .map(|(name, version)| -> Result<RootResolution> {
+            // Resolve the manifest constraint (exact pin, series pin, or "*")
+            // against what Homebrew actually offers
+            let spec = crate::version::resolve_spec(name, version)?;
+            let info = homebrew::package_info(&spec)?;
+            let sha256 = homebrew::package_prefix(&info.formula)
+                .and_then(|prefix| homebrew::hash_package_files(&prefix))
+                .ok();
+            let deps = homebrew::package_deps(&spec)?;
+            Ok(RootResolution {
+                name: (*name).clone(),
+                info,
+                sha256,
+                deps,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    root_results.sort_by(|a, b| a.name.cmp(&b.name));
+
+    // Dedupe dependencies shared across roots so each formula is queried
+    // only once, then resolve the unique set concurrently.
+    let unique_deps: BTreeSet<String> = root_results
+        .iter()
+        .flat_map(|r| r.deps.iter().cloned())
+        .collect();
+
+    let dep_results: HashMap<String, DepResolution> = unique_deps
+        .par_iter()
+        .filter_map(|dep| {
+            let (info, sha256) = resolve_for_lock(dep)?;
+            Some(DepResolution {
+                spec: dep.clone(),
+                info,
+                sha256,
+            })
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .map(|res| (res.spec.clone(), res))
+        .collect();
+
+    for root in &root_results {
+        lock.add_package(
+            root.name.clone(),
+            root.info.version.clone(),
+            root.info.formula.clone(),
+            root.sha256.clone(),
+        );
+        println!("    Locked {} @ {}", root.name, root.info.version);
+
+        if !root.deps.is_empty() {
+            println!("      Locking {} dependencies...", root.deps.len());
+            let mut deps = root.deps.clone();
+            deps.sort();
             for dep in deps {
-                if let Ok(dep_info) = homebrew::package_info(&dep) {
+                if let Some(dep_res) = dep_results.get(&dep) {
                     lock.add_dependency(
-                        name.clone(),
+                        root.name.clone(),
                         dep,
-                        dep_info.version,
-                        dep_info.formula,
+                        dep_res.info.version.clone(),
+                        dep_res.info.formula.clone(),
+                        dep_res.sha256.clone(),
                     );
                 }
             }
         }
     }
 
+    if let Some(previous) = &previous_lock {
+        let diff = previous.diff(&lock);
+        if !diff.is_empty() {
+            println!();
+            diff.print();
+        }
+    }
+
     lock.save()?;
     println!("  {} Lock file saved", "✓".green());
     Ok(())
@@ -9,7 +9,7 @@ pub fn enter() -> Result<()> {
     // Only run install if not already in a macdev shell (avoid conflicts)
     if env::var("MACDEV_ACTIVE").is_err() {
         println!("{}", "Ensuring environment is up to date...".cyan());
-        crate::environment::install()?;
+        crate::environment::install(false, false, false)?;
         println!();
     }
 
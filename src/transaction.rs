@@ -0,0 +1,94 @@
+use colored::*;
+
+use crate::homebrew;
+
+/// A single reversible brew mutation performed during a run
+enum Action {
+    Installed(String),
+    InstalledCask(String),
+    Unlinked(String),
+}
+
+/// Tracks brew mutations performed during an install/apply run so they can
+/// be undone on failure.
+///
+/// Mirrors the rollback-on-drop pattern cargo uses for `cargo install`:
+/// every successful mutation is recorded with `record_*`, and `Drop` unwinds
+/// them best-effort (uninstalling, relinking) unless `success()` has already
+/// cleared the list. Callers should only persist the `Lock` after calling
+/// `success()`, so the manifest, lockfile, and actual brew state never
+/// diverge on error.
+///
+/// Taps are deliberately not tracked here: `macdev tap`/`untap` process a
+/// batch of taps independently and report partial success rather than
+/// aborting as a unit, so an all-or-nothing rollback would fight their
+/// own contract.
+pub struct Transaction {
+    actions: Vec<Action>,
+}
+
+impl Transaction {
+    pub fn new() -> Self {
+        Transaction { actions: Vec::new() }
+    }
+
+    /// Record that a package was freshly installed via Homebrew
+    pub fn record_install(&mut self, package: String) {
+        self.actions.push(Action::Installed(package));
+    }
+
+    /// Record that a cask was freshly installed via Homebrew
+    pub fn record_cask_install(&mut self, cask: String) {
+        self.actions.push(Action::InstalledCask(cask));
+    }
+
+    /// Record that a package was unlinked for isolation
+    pub fn record_unlink(&mut self, package: String) {
+        self.actions.push(Action::Unlinked(package));
+    }
+
+    /// Commit the transaction: the run succeeded, so clear the recorded
+    /// actions and make `Drop` a no-op.
+    pub fn success(&mut self) {
+        self.actions.clear();
+    }
+}
+
+impl Default for Transaction {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Transaction {
+    fn drop(&mut self) {
+        if self.actions.is_empty() {
+            return;
+        }
+
+        println!("{}", "Rolling back partial changes...".red().bold());
+
+        for action in self.actions.drain(..).rev() {
+            match action {
+                Action::Installed(package) => {
+                    println!("  {} {}", "Undoing install of".red(), package);
+                    if let Err(e) = homebrew::uninstall_package(&package) {
+                        eprintln!("    {} Failed to roll back {}: {}", "⚠".yellow(), package, e);
+                    }
+                }
+                Action::InstalledCask(cask) => {
+                    println!("  {} {}", "Undoing install of".red(), cask);
+                    if let Err(e) = homebrew::uninstall_cask(&cask) {
+                        eprintln!("    {} Failed to roll back {}: {}", "⚠".yellow(), cask, e);
+                    }
+                }
+                Action::Unlinked(package) => {
+                    println!("  {} {}", "Relinking".red(), package);
+                    if let Err(e) = homebrew::relink_package(&package) {
+                        eprintln!("    {} Failed to relink {}: {}", "⚠".yellow(), package, e);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use anyhow::{Context, Result};
+
+use crate::homebrew;
+
+/// A dot-separated version broken into comparable numeric/text components,
+/// so `3.11.7` sorts after `3.9` instead of before it lexicographically.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Version {
+    parts: Vec<VersionPart>,
+    raw: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum VersionPart {
+    Numeric(u64),
+    // Non-numeric suffixes (e.g. "rc1") rank below all-numeric parts of the
+    // same position, so "3.11" outranks "3.11rc1".
+    Text(String),
+}
+
+impl Ord for VersionPart {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        match (self, other) {
+            (VersionPart::Numeric(a), VersionPart::Numeric(b)) => a.cmp(b),
+            (VersionPart::Text(a), VersionPart::Text(b)) => a.cmp(b),
+            (VersionPart::Numeric(_), VersionPart::Text(_)) => std::cmp::Ordering::Greater,
+            (VersionPart::Text(_), VersionPart::Numeric(_)) => std::cmp::Ordering::Less,
+        }
+    }
+}
+
+impl PartialOrd for VersionPart {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Version {
+    fn parse(raw: &str) -> Self {
+        let parts = raw
+            .split(['.', '_', '-'])
+            .map(|segment| match segment.parse::<u64>() {
+                Ok(n) => VersionPart::Numeric(n),
+                Err(_) => VersionPart::Text(segment.to_string()),
+            })
+            .collect();
+
+        Version { parts, raw: raw.to_string() }
+    }
+
+    /// Whether this version belongs to the series named by `prefix`
+    /// (e.g. `3.11.7` matches the series `3.11`)
+    fn matches_series(&self, prefix: &str) -> bool {
+        self.raw == prefix || self.raw.starts_with(&format!("{}.", prefix))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.parts.cmp(&other.parts)
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Resolve a manifest version constraint against the set of versions
+/// Homebrew can offer, picking the concrete version to lock.
+///
+/// - `"*"` resolves to the newest of `candidates`
+/// - an exact pin (e.g. `"3.11.7"`) must appear verbatim in `candidates`
+/// - a series pin (e.g. `"3.11"`) resolves to the newest `3.11.x` in `candidates`
+///
+/// Candidates are sorted descending (newest first), mirroring uv's
+/// installation-key ordering, so ties and ambiguous suffixes resolve
+/// deterministically to the newest compatible version.
+pub fn resolve(constraint: &str, candidates: &[String]) -> Result<String> {
+    if candidates.is_empty() {
+        anyhow::bail!(
+            "No available versions found to resolve constraint '{}'",
+            constraint
+        );
+    }
+
+    let mut parsed: Vec<Version> = candidates.iter().map(|c| Version::parse(c)).collect();
+    parsed.sort();
+    parsed.reverse();
+
+    let best = if constraint == "*" {
+        parsed.into_iter().next()
+    } else if candidates.iter().any(|c| c == constraint) {
+        parsed.into_iter().find(|v| v.raw == constraint)
+    } else {
+        parsed.into_iter().find(|v| v.matches_series(constraint))
+    };
+
+    best.map(|v| v.raw).with_context(|| {
+        format!(
+            "No version satisfying '{}'. Available versions: {}",
+            constraint,
+            candidates.join(", ")
+        )
+    })
+}
+
+/// Whether `candidate` is a strictly newer version than `current`
+pub fn is_newer(current: &str, candidate: &str) -> bool {
+    Version::parse(candidate) > Version::parse(current)
+}
+
+/// Whether `version` satisfies a manifest constraint, using the same
+/// matching rules `resolve` uses to pick a candidate: `"*"` accepts
+/// anything, an exact pin must match verbatim, and a series pin accepts
+/// any version in that series.
+pub fn satisfies(constraint: &str, version: &str) -> bool {
+    constraint == "*" || version == constraint || Version::parse(version).matches_series(constraint)
+}
+
+/// Newest version among `candidates`, with no series constraint applied —
+/// "latest overall" as opposed to `resolve`'s "latest matching this pin".
+pub fn latest(candidates: &[String]) -> Option<String> {
+    candidates.iter().map(|c| Version::parse(c)).max().map(|v| v.raw)
+}
+
+/// Resolve a manifest package name + constraint into the concrete brew
+/// formula spec to install (e.g. `"python"` + `"3.11"` -> `"python@3.11"`).
+pub fn resolve_spec(name: &str, constraint: &str) -> Result<String> {
+    if constraint == "*" {
+        return Ok(name.to_string());
+    }
+
+    let candidates = homebrew::available_versions(name)?;
+    let resolved = resolve(constraint, &candidates)?;
+    Ok(format!("{}@{}", name, resolved))
+}
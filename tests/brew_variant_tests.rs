@@ -0,0 +1,41 @@
+use macdev::brew_variant::BrewVariant;
+use std::path::PathBuf;
+
+#[test]
+fn test_parse_arm_aliases() {
+    assert_eq!(BrewVariant::parse("arm"), BrewVariant::MacArm);
+    assert_eq!(BrewVariant::parse("macarm"), BrewVariant::MacArm);
+    assert_eq!(BrewVariant::parse("apple-silicon"), BrewVariant::MacArm);
+}
+
+#[test]
+fn test_parse_intel_aliases() {
+    assert_eq!(BrewVariant::parse("intel"), BrewVariant::MacIntel);
+    assert_eq!(BrewVariant::parse("macintel"), BrewVariant::MacIntel);
+}
+
+#[test]
+fn test_parse_explicit_path() {
+    assert_eq!(
+        BrewVariant::parse("/custom/homebrew"),
+        BrewVariant::Path(PathBuf::from("/custom/homebrew"))
+    );
+}
+
+#[test]
+fn test_prefix_matches_variant() {
+    assert_eq!(BrewVariant::MacArm.prefix(), PathBuf::from("/opt/homebrew"));
+    assert_eq!(BrewVariant::MacIntel.prefix(), PathBuf::from("/usr/local"));
+}
+
+#[test]
+fn test_select_prefers_pinned_value() {
+    let selected = BrewVariant::select(Some("intel"));
+    assert_eq!(selected, BrewVariant::MacIntel);
+}
+
+#[test]
+fn test_display_round_trips_through_parse() {
+    assert_eq!(BrewVariant::parse(&BrewVariant::MacArm.to_string()), BrewVariant::MacArm);
+    assert_eq!(BrewVariant::parse(&BrewVariant::MacIntel.to_string()), BrewVariant::MacIntel);
+}
@@ -1,4 +1,5 @@
-use macdev::manifest::Lock;
+use macdev::manifest::{Lock, Manifest};
+use std::collections::HashMap;
 
 #[test]
 fn test_lock_new() {
@@ -19,12 +20,29 @@ fn test_lock_add_package() {
         "python".to_string(),
         "3.11.7".to_string(),
         "python@3.11".to_string(),
+        None,
     );
 
     assert_eq!(lock.packages.len(), 1);
     let pkg = lock.packages.get("python").unwrap();
     assert_eq!(pkg.version, "3.11.7");
     assert_eq!(pkg.formula, "python@3.11");
+    assert_eq!(pkg.sha256, None);
+}
+
+#[test]
+fn test_lock_add_package_with_sha256() {
+    let mut lock = Lock::new();
+
+    lock.add_package(
+        "python".to_string(),
+        "3.11.7".to_string(),
+        "python@3.11".to_string(),
+        Some("deadbeef".to_string()),
+    );
+
+    let pkg = lock.packages.get("python").unwrap();
+    assert_eq!(pkg.sha256.as_deref(), Some("deadbeef"));
 }
 
 #[test]
@@ -36,6 +54,7 @@ fn test_lock_add_dependency() {
         "readline".to_string(),
         "8.3.1".to_string(),
         "readline".to_string(),
+        None,
     );
 
     assert_eq!(lock.dependencies.len(), 1);
@@ -53,6 +72,7 @@ fn test_lock_multiple_dependencies() {
         "readline".to_string(),
         "8.3.1".to_string(),
         "readline".to_string(),
+        None,
     );
 
     lock.add_dependency(
@@ -60,6 +80,7 @@ fn test_lock_multiple_dependencies() {
         "sqlite".to_string(),
         "3.51.0".to_string(),
         "sqlite".to_string(),
+        None,
     );
 
     assert_eq!(lock.dependencies.len(), 2);
@@ -75,6 +96,7 @@ fn test_lock_serialization() {
         "python".to_string(),
         "3.11.7".to_string(),
         "python@3.11".to_string(),
+        None,
     );
 
     lock.add_dependency(
@@ -82,6 +104,7 @@ fn test_lock_serialization() {
         "readline".to_string(),
         "8.3.1".to_string(),
         "readline".to_string(),
+        None,
     );
 
     let toml_str = toml::to_string_pretty(&lock).unwrap();
@@ -91,6 +114,7 @@ fn test_lock_serialization() {
     assert!(toml_str.contains("version = \"3.11.7\""));
     assert!(toml_str.contains("formula = \"python@3.11\""));
     assert!(toml_str.contains("[dependencies.\"python:readline\"]"));
+    assert!(!toml_str.contains("sha256"));
 }
 
 #[test]
@@ -117,8 +141,118 @@ fn test_lock_deserialization() {
     let pkg = lock.packages.get("python").unwrap();
     assert_eq!(pkg.version, "3.11.7");
     assert_eq!(pkg.formula, "python@3.11");
+    assert_eq!(pkg.sha256, None);
 
     assert_eq!(lock.dependencies.len(), 1);
     let dep = lock.dependencies.get("python:readline").unwrap();
     assert_eq!(dep.version, "8.3.1");
 }
+
+#[test]
+fn test_lock_deserialization_with_sha256() {
+    let toml_str = r#"
+        [metadata]
+        generated = "2025-11-20T18:25:24.105781+00:00"
+        macdev_version = "0.1.0"
+
+        [packages.python]
+        version = "3.11.7"
+        formula = "python@3.11"
+        sha256 = "deadbeef"
+    "#;
+
+    let lock: Lock = toml::from_str(toml_str).unwrap();
+
+    let pkg = lock.packages.get("python").unwrap();
+    assert_eq!(pkg.sha256.as_deref(), Some("deadbeef"));
+}
+
+fn manifest_with_packages(packages: &[(&str, &str)]) -> Manifest {
+    let mut map = HashMap::new();
+    for (name, version) in packages {
+        map.insert(name.to_string(), version.to_string());
+    }
+    Manifest { packages: map, ..Default::default() }
+}
+
+#[test]
+fn test_lock_diff_added_removed_changed() {
+    let mut old = Lock::new();
+    old.add_package("python".to_string(), "3.11.7".to_string(), "python@3.11".to_string(), None);
+    old.add_package("node".to_string(), "20.0.0".to_string(), "node@20".to_string(), None);
+
+    let mut new = Lock::new();
+    new.add_package("python".to_string(), "3.12.0".to_string(), "python@3.12".to_string(), None);
+    new.add_package("rust".to_string(), "1.80.0".to_string(), "rust".to_string(), None);
+
+    let diff = old.diff(&new);
+
+    assert_eq!(diff.added, vec![("rust".to_string(), "1.80.0".to_string())]);
+    assert_eq!(diff.removed, vec![("node".to_string(), "20.0.0".to_string())]);
+    assert_eq!(
+        diff.changed,
+        vec![("python".to_string(), "3.11.7".to_string(), "3.12.0".to_string())]
+    );
+    assert!(!diff.is_empty());
+}
+
+#[test]
+fn test_lock_diff_identical_is_empty() {
+    let mut lock = Lock::new();
+    lock.add_package("python".to_string(), "3.11.7".to_string(), "python@3.11".to_string(), None);
+
+    let diff = lock.diff(&lock);
+
+    assert!(diff.is_empty());
+}
+
+#[test]
+fn test_is_consistent_with_matching_exact_pin() {
+    let mut lock = Lock::new();
+    lock.add_package("python".to_string(), "3.11.7".to_string(), "python@3.11".to_string(), None);
+
+    let manifest = manifest_with_packages(&[("python", "3.11.7")]);
+
+    assert!(lock.is_consistent_with(&manifest));
+}
+
+#[test]
+fn test_is_consistent_with_matching_series_pin() {
+    let mut lock = Lock::new();
+    lock.add_package("python".to_string(), "3.11.7".to_string(), "python@3.11".to_string(), None);
+
+    let manifest = manifest_with_packages(&[("python", "3.11")]);
+
+    assert!(lock.is_consistent_with(&manifest));
+}
+
+#[test]
+fn test_is_consistent_with_version_drift() {
+    let mut lock = Lock::new();
+    lock.add_package("python".to_string(), "3.11.7".to_string(), "python@3.11".to_string(), None);
+
+    let manifest = manifest_with_packages(&[("python", "3.12")]);
+
+    assert!(!lock.is_consistent_with(&manifest));
+}
+
+#[test]
+fn test_is_consistent_with_package_added_to_manifest() {
+    let mut lock = Lock::new();
+    lock.add_package("python".to_string(), "3.11.7".to_string(), "python@3.11".to_string(), None);
+
+    let manifest = manifest_with_packages(&[("python", "3.11.7"), ("node", "*")]);
+
+    assert!(!lock.is_consistent_with(&manifest));
+}
+
+#[test]
+fn test_is_consistent_with_package_removed_from_manifest() {
+    let mut lock = Lock::new();
+    lock.add_package("python".to_string(), "3.11.7".to_string(), "python@3.11".to_string(), None);
+    lock.add_package("node".to_string(), "20.0.0".to_string(), "node@20".to_string(), None);
+
+    let manifest = manifest_with_packages(&[("python", "3.11.7")]);
+
+    assert!(!lock.is_consistent_with(&manifest));
+}
@@ -1,4 +1,4 @@
-use macdev::manifest::Manifest;
+use macdev::manifest::{Manifest, TapEntry};
 
 #[test]
 fn test_manifest_default() {
@@ -30,10 +30,27 @@ fn test_manifest_add_impure() {
 #[test]
 fn test_manifest_add_tap() {
     let mut manifest = Manifest::default();
-    manifest.add_tap("homebrew/cask".to_string());
+    manifest.add_tap("homebrew/cask".to_string(), None, false);
 
     assert_eq!(manifest.taps.len(), 1);
-    assert_eq!(manifest.taps.get("homebrew/cask"), Some(&true));
+    assert_eq!(
+        manifest.taps.get("homebrew/cask"),
+        Some(&TapEntry { url: None, full: false })
+    );
+}
+
+#[test]
+fn test_manifest_add_tap_with_custom_url() {
+    let mut manifest = Manifest::default();
+    manifest.add_tap(
+        "acme/formulae".to_string(),
+        Some("git@github.com:acme/homebrew-formulae.git".to_string()),
+        true,
+    );
+
+    let entry = manifest.taps.get("acme/formulae").unwrap();
+    assert_eq!(entry.url.as_deref(), Some("git@github.com:acme/homebrew-formulae.git"));
+    assert!(entry.full);
 }
 
 #[test]
@@ -48,10 +65,20 @@ fn test_manifest_remove_package() {
     assert!(manifest.impure.is_empty());
 }
 
+#[test]
+fn test_manifest_set_arch() {
+    let mut manifest = Manifest::default();
+    assert_eq!(manifest.arch, None);
+
+    manifest.set_arch("arm".to_string());
+
+    assert_eq!(manifest.arch, Some("arm".to_string()));
+}
+
 #[test]
 fn test_manifest_remove_tap() {
     let mut manifest = Manifest::default();
-    manifest.add_tap("homebrew/cask".to_string());
+    manifest.add_tap("homebrew/cask".to_string(), None, false);
 
     manifest.remove_tap("homebrew/cask");
 
@@ -0,0 +1,121 @@
+use macdev::version::{is_newer, latest, resolve, resolve_spec, satisfies};
+
+#[test]
+fn test_is_newer_patch_bump() {
+    assert!(is_newer("3.11.6", "3.11.7"));
+    assert!(!is_newer("3.11.7", "3.11.6"));
+}
+
+#[test]
+fn test_is_newer_minor_outranks_patch() {
+    assert!(is_newer("3.9.5", "3.11.0"));
+}
+
+#[test]
+fn test_is_newer_equal_versions() {
+    assert!(!is_newer("1.2.3", "1.2.3"));
+}
+
+#[test]
+fn test_is_newer_numeric_outranks_text_suffix() {
+    // A final release must outrank a pre-release suffix in the same
+    // position, e.g. "3.11" over "3.11rc1".
+    assert!(!is_newer("3.11.7", "3.11.7rc1"));
+    assert!(is_newer("3.11.7rc1", "3.11.7"));
+}
+
+#[test]
+fn test_latest_picks_newest_regardless_of_series() {
+    let candidates = vec!["3.11.7".to_string(), "3.13.0".to_string(), "3.9.5".to_string()];
+    assert_eq!(latest(&candidates), Some("3.13.0".to_string()));
+}
+
+#[test]
+fn test_latest_empty_candidates() {
+    assert_eq!(latest(&[]), None);
+}
+
+#[test]
+fn test_latest_prefers_final_release_over_text_suffix() {
+    let candidates = vec!["3.11.7rc1".to_string(), "3.11.7".to_string()];
+    assert_eq!(latest(&candidates), Some("3.11.7".to_string()));
+}
+
+#[test]
+fn test_satisfies_wildcard_accepts_anything() {
+    assert!(satisfies("*", "3.11.7"));
+}
+
+#[test]
+fn test_satisfies_exact_pin_match() {
+    assert!(satisfies("3.11.7", "3.11.7"));
+}
+
+#[test]
+fn test_satisfies_exact_pin_mismatch() {
+    assert!(!satisfies("3.11.7", "3.11.8"));
+}
+
+#[test]
+fn test_satisfies_series_pin_match() {
+    assert!(satisfies("3.11", "3.11.7"));
+}
+
+#[test]
+fn test_satisfies_series_pin_mismatch() {
+    assert!(!satisfies("3.11", "3.12.0"));
+    assert!(!satisfies("3.1", "3.11.7"));
+}
+
+fn candidates(versions: &[&str]) -> Vec<String> {
+    versions.iter().map(|v| v.to_string()).collect()
+}
+
+#[test]
+fn test_resolve_wildcard_picks_newest() {
+    let result = resolve("*", &candidates(&["3.9.5", "3.13.0", "3.11.7"])).unwrap();
+    assert_eq!(result, "3.13.0");
+}
+
+#[test]
+fn test_resolve_exact_pin() {
+    let result = resolve("3.11.7", &candidates(&["3.9.5", "3.11.7", "3.13.0"])).unwrap();
+    assert_eq!(result, "3.11.7");
+}
+
+#[test]
+fn test_resolve_exact_pin_not_available_errors() {
+    let result = resolve("3.11.9", &candidates(&["3.9.5", "3.11.7"]));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_resolve_series_pin_picks_newest_in_series() {
+    let result = resolve("3.11", &candidates(&["3.11.5", "3.11.7", "3.12.0"])).unwrap();
+    assert_eq!(result, "3.11.7");
+}
+
+#[test]
+fn test_resolve_series_pin_does_not_match_shorter_series() {
+    // "3.1" must not match "3.11.x" candidates
+    let result = resolve("3.1", &candidates(&["3.11.7", "3.1.2"])).unwrap();
+    assert_eq!(result, "3.1.2");
+}
+
+#[test]
+fn test_resolve_no_candidates_errors() {
+    let result = resolve("3.11", &candidates(&[]));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_resolve_no_matching_candidate_errors() {
+    let result = resolve("3.12", &candidates(&["3.9.5", "3.11.7"]));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_resolve_spec_wildcard_returns_bare_name() {
+    let result = resolve_spec("python", "*").unwrap();
+    assert_eq!(result, "python");
+}